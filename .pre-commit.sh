@@ -35,8 +35,17 @@ cargo sort -c
 cargo rustdoc --all-features -- -D warnings
 cargo test --all-targets --no-default-features
 cargo test --all-targets --no-default-features --features json
+cargo test --all-targets --no-default-features --features json5
 cargo test --all-targets --no-default-features --features toml
 cargo test --all-targets --no-default-features --features yaml
+cargo test --all-targets --no-default-features --features edit,toml
+cargo test --all-targets --no-default-features --features edit,yaml
+cargo test --all-targets --no-default-features --features miette
+cargo test --all-targets --no-default-features --features ron
+cargo test --all-targets --no-default-features --features mmd
+cargo test --all-targets --no-default-features --features async,yaml
+cargo test --all-targets --no-default-features --features cache,yaml
+cargo test --all-targets --no-default-features --features watch,yaml
 cargo test --all-targets --all-features
 cargo test --doc --all-features
 cargo clippy --all-features --all-targets -- -D warnings
@@ -0,0 +1,79 @@
+//! Compares `parse` with the two-pass approach of deserializing into a
+//! format-specific value first and converting it into the target type.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use markdown_frontmatter::FrontmatterFormat;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Frontmatter {
+    title: String,
+    description: String,
+    draft: bool,
+    weight: i64,
+    tags: Vec<String>,
+    authors: Vec<Author>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Author {
+    name: String,
+    email: String,
+}
+
+fn document(format: FrontmatterFormat) -> String {
+    let frontmatter = Frontmatter {
+        title: "A benchmark document".into(),
+        description: "Lorem ipsum dolor sit amet, consectetur adipiscing elit".repeat(4),
+        draft: false,
+        weight: 42,
+        tags: (0..32).map(|i| format!("tag-{i}")).collect(),
+        authors: (0..16)
+            .map(|i| Author {
+                name: format!("Author {i}"),
+                email: format!("author{i}@example.com"),
+            })
+            .collect(),
+    };
+    markdown_frontmatter::to_string(&frontmatter, format, "# Body\n").unwrap()
+}
+
+fn two_pass(doc: &str) -> Frontmatter {
+    let (frontmatter, _) = markdown_frontmatter::split(doc).unwrap();
+    let frontmatter = frontmatter.unwrap();
+    let matter = frontmatter.as_str();
+    match frontmatter.format() {
+        FrontmatterFormat::Json => {
+            serde_json::from_value(serde_json::from_str(matter).unwrap()).unwrap()
+        }
+        FrontmatterFormat::Toml => toml::from_str::<toml::Value>(matter)
+            .unwrap()
+            .try_into()
+            .unwrap(),
+        FrontmatterFormat::Yaml => {
+            serde_yaml::from_value(serde_yaml::from_str(matter).unwrap()).unwrap()
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn bench(c: &mut Criterion) {
+    for format in [
+        FrontmatterFormat::Json,
+        FrontmatterFormat::Toml,
+        FrontmatterFormat::Yaml,
+    ] {
+        let doc = document(format);
+        let mut group = c.benchmark_group(<&str>::from(format));
+        group.bench_function("parse", |b| {
+            b.iter(|| markdown_frontmatter::parse::<Frontmatter>(black_box(&doc)).unwrap())
+        });
+        group.bench_function("two_pass", |b| b.iter(|| two_pass(black_box(&doc))));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
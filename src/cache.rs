@@ -0,0 +1,296 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{Error, Parser};
+
+/// Version of the cache file layout, caches of other versions are discarded.
+const VERSION: u32 = 1;
+
+/// An on-disk cache of parsed frontmatter, so only changed documents are
+/// parsed again.
+///
+/// Entries are keyed by the path of a document and validated by the length
+/// and FNV-1a hash of its content. Frontmatter is stored as JSON, so it has to
+/// be serializable and comparable: it's cached only if it's deserialized back
+/// from JSON unchanged. Frontmatter that doesn't survive the round trip, e.g.
+/// with YAML tags or non-finite floats, is parsed every time. The cache
+/// doesn't know the parser configuration, keep a separate cache file per
+/// configuration.
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_frontmatter::Cache;
+///
+/// #[derive(PartialEq, serde::Serialize, serde::Deserialize)]
+/// struct Frontmatter {
+///     title: String,
+/// }
+///
+/// let mut cache = Cache::load("target/frontmatter.json").unwrap();
+/// for path in ["index.md", "about.md"] {
+///     let content = std::fs::read_to_string(path).unwrap();
+///     let (frontmatter, body) = cache.parse::<Frontmatter>(path, &content).unwrap();
+/// }
+/// cache.prune();
+/// cache.save("target/frontmatter.json").unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct Cache {
+    parser: Parser,
+    entries: BTreeMap<PathBuf, Entry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    len: usize,
+    hash: u64,
+    body_start: usize,
+    frontmatter: serde_json::Value,
+    /// The entry was used since the cache was loaded.
+    #[serde(skip)]
+    used: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile<E> {
+    version: u32,
+    entries: E,
+}
+
+impl Cache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a cache saved by [`save`](Self::save).
+    ///
+    /// A missing file, as well as a corrupted one or one saved by another
+    /// version of the crate, results in an empty cache.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let json = match fs::read(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(Error::Io(e)),
+        };
+        let entries = serde_json::from_slice(&json)
+            .ok()
+            .filter(|file: &CacheFile<_>| file.version == VERSION)
+            .map(|file| file.entries)
+            .unwrap_or_default();
+        Ok(Self {
+            parser: Parser::new(),
+            entries,
+        })
+    }
+
+    /// Saves the cache, replacing the file at once, so a cache interrupted
+    /// while saving isn't corrupted.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let file = CacheFile {
+            version: VERSION,
+            entries: &self.entries,
+        };
+        let json = serde_json::to_vec(&file).map_err(|e| Error::Io(e.into()))?;
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, json)
+            .and_then(|()| fs::rename(&tmp, path))
+            .map_err(Error::Io)
+    }
+
+    /// Sets the parser used for documents absent from the cache.
+    pub fn parser(mut self, parser: Parser) -> Self {
+        self.parser = parser;
+        self
+    }
+
+    /// Parses frontmatter of a document like [`Parser::parse`], returning the
+    /// cached frontmatter if the content hasn't changed.
+    pub fn parse<'a, T: Serialize + DeserializeOwned + PartialEq>(
+        &mut self,
+        path: impl AsRef<Path>,
+        content: &'a str,
+    ) -> Result<(T, &'a str), Error> {
+        let path = path.as_ref();
+        let hash = fnv1a(content.as_bytes());
+        if let Some(entry) = self.entries.get_mut(path)
+            && entry.len == content.len()
+            && entry.hash == hash
+            && let Some(body) = content.get(entry.body_start..)
+            // The frontmatter type may have changed
+            && let Ok(frontmatter) = T::deserialize(&entry.frontmatter)
+        {
+            entry.used = true;
+            return Ok((frontmatter, body));
+        }
+
+        let (frontmatter, body) = self.parser.parse::<T>(content)?;
+        let value = serde_json::to_value(&frontmatter)
+            .ok()
+            .filter(|value| T::deserialize(value).is_ok_and(|cached| cached == frontmatter));
+        match value {
+            Some(value) => {
+                let entry = Entry {
+                    len: content.len(),
+                    hash,
+                    body_start: content.len() - body.len(),
+                    frontmatter: value,
+                    used: true,
+                };
+                self.entries.insert(path.to_owned(), entry);
+            }
+            None => {
+                self.entries.remove(path);
+            }
+        }
+        Ok((frontmatter, body))
+    }
+
+    /// Removes entries of documents not parsed since the cache was loaded,
+    /// e.g. of deleted documents.
+    pub fn prune(&mut self) {
+        self.entries.retain(|_, entry| entry.used);
+    }
+}
+
+/// 64-bit FNV-1a hash.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{BoxError, Format, Value};
+
+    /// A title between `%%%` delimiters, counting parsed documents.
+    #[derive(Debug)]
+    struct Counting(AtomicUsize);
+
+    impl Format for Counting {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn delimiters(&self) -> (&'static str, &'static str) {
+            ("%%%", "%%%")
+        }
+
+        fn parse(&self, matter: &str) -> Result<Value, BoxError> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            let title = Value::String(matter.trim().into());
+            Ok(Value::Table(
+                [("title".into(), title)].into_iter().collect(),
+            ))
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Frontmatter {
+        title: String,
+    }
+
+    fn cache(format: &'static Counting) -> Cache {
+        Cache::new().parser(Parser::new().format(format))
+    }
+
+    fn parse(cache: &mut Cache, path: &str, content: &str) -> (String, String) {
+        let (frontmatter, body) = cache.parse::<Frontmatter>(path, content).unwrap();
+        (frontmatter.title, body.into())
+    }
+
+    #[test]
+    fn parses_changed_documents() {
+        static FORMAT: Counting = Counting(AtomicUsize::new(0));
+        let mut cache = cache(&FORMAT);
+        let doc = "%%%\nHello\n%%%\nWorld";
+        for _ in 0..2 {
+            assert_eq!(
+                parse(&mut cache, "a.md", doc),
+                ("Hello".into(), "World".into())
+            );
+        }
+        assert_eq!(FORMAT.0.load(Ordering::Relaxed), 1);
+
+        parse(&mut cache, "b.md", doc);
+        assert_eq!(FORMAT.0.load(Ordering::Relaxed), 2);
+
+        let doc = "%%%\nHello!\n%%%\nWorld";
+        assert_eq!(parse(&mut cache, "a.md", doc).0, "Hello!");
+        assert_eq!(FORMAT.0.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn save_and_load() {
+        static FORMAT: Counting = Counting(AtomicUsize::new(0));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let doc = "%%%\nHello\n%%%\nWorld";
+
+        let mut cache = cache(&FORMAT);
+        parse(&mut cache, "a.md", doc);
+        parse(&mut cache, "b.md", doc);
+        cache.save(&path).unwrap();
+
+        let mut cache = Cache::load(&path)
+            .unwrap()
+            .parser(Parser::new().format(&FORMAT));
+        assert_eq!(
+            parse(&mut cache, "a.md", doc),
+            ("Hello".into(), "World".into())
+        );
+        assert_eq!(FORMAT.0.load(Ordering::Relaxed), 2);
+
+        cache.prune();
+        assert_eq!(
+            cache.entries.keys().collect::<Vec<_>>(),
+            [Path::new("a.md")]
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn lossy_frontmatter() {
+        let mut cache = Cache::new();
+        let doc = "---\nfoo: !bar baz\nn: .nan\n---\nWorld";
+        let (fresh, _) = Parser::new().parse::<Value>(doc).unwrap();
+        for _ in 0..2 {
+            let (cached, body) = cache.parse::<Value>("a.md", doc).unwrap();
+            assert_eq!(cached.get("foo"), fresh.get("foo"));
+            assert!(cached.get("n").and_then(Value::as_float).unwrap().is_nan());
+            assert_eq!(body, "World");
+        }
+        assert!(cache.entries.is_empty());
+
+        let doc = "---\nfoo: bar\nn: 1.5\n---\nWorld";
+        let (fresh, _) = Parser::new().parse::<Value>(doc).unwrap();
+        for _ in 0..2 {
+            assert_eq!(cache.parse::<Value>("a.md", doc).unwrap().0, fresh);
+        }
+        assert!(cache.entries.contains_key(Path::new("a.md")));
+    }
+
+    #[test]
+    fn load_missing_or_corrupted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        assert!(Cache::load(&path).unwrap().entries.is_empty());
+
+        for json in ["{", r#"{"version":0,"entries":{"a.md":{}}}"#] {
+            fs::write(&path, json).unwrap();
+            assert!(Cache::load(&path).unwrap().entries.is_empty());
+        }
+    }
+}
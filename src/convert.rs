@@ -0,0 +1,288 @@
+use crate::{
+    Error, FrontmatterFormat, split, to_string,
+    value::{Table, Value},
+};
+
+/// Converts the frontmatter of a document into another format, leaving the
+/// body untouched.
+///
+/// Documents without frontmatter or with frontmatter already in the target
+/// format are returned as is. Values that can't be represented in the target
+/// format, e.g. `null` in TOML or TOML datetimes in JSON, are reported as
+/// [`Error::Unrepresentable`] rather than dropped. TOML datetimes converted
+/// into YAML or RON become plain strings, tables converted into RON become a
+/// struct, so their keys have to be RON identifiers.
+///
+/// # Examples
+///
+/// ```
+/// use markdown_frontmatter::{FrontmatterFormat, convert};
+///
+/// let doc = "---\ntitle: Hello\ntags: [a, b]\n---\nWorld";
+///
+/// let doc = convert(doc, FrontmatterFormat::Toml).unwrap();
+/// assert_eq!(doc, "+++\ntitle = \"Hello\"\ntags = [\"a\", \"b\"]\n+++\nWorld");
+/// ```
+pub fn convert(content: &str, format: FrontmatterFormat) -> Result<String, Error> {
+    let (frontmatter, body) = split(content)?;
+    let Some(frontmatter) = frontmatter.filter(|frontmatter| frontmatter.format() != format) else {
+        return Ok(content.to_owned());
+    };
+    let value = match frontmatter.parse(content)? {
+        // Empty YAML frontmatter
+        Value::Null => Value::Table(Table::default()),
+        value => value,
+    };
+    let value = prepare(value, format, "")?;
+    #[cfg(feature = "ron")]
+    if let (Value::Table(table), FrontmatterFormat::Ron) = (&value, format) {
+        return to_string(&ron_struct(table)?, format, body);
+    }
+    to_string(&value, format, body)
+}
+
+/// Writes a table as a RON struct, as tables are serialized as RON maps,
+/// while frontmatter has to be a struct.
+#[cfg(feature = "ron")]
+fn ron_struct(table: &Table) -> Result<Box<ron::value::RawValue>, Error> {
+    let mut ron = String::from("(\n");
+    for (key, value) in table.iter() {
+        if !is_ron_identifier(key) {
+            return Err(Error::Unrepresentable(
+                "key",
+                key.clone(),
+                FrontmatterFormat::Ron.into(),
+            ));
+        }
+        let value = crate::ron_options()
+            .to_string_pretty(value, ron::ser::PrettyConfig::default())
+            .map_err(Error::SerializeRon)?;
+        // Fields are indented, so are the nested lines of their values
+        let raw = if is_plain_ron_identifier(key) {
+            ""
+        } else {
+            "r#"
+        };
+        let value = value.replace('\n', "\n    ");
+        ron.push_str(&format!("    {raw}{key}: {value},\n"));
+    }
+    ron.push(')');
+    ron::value::RawValue::from_boxed_ron(ron.into_boxed_str())
+        .map_err(|e| Error::SerializeRon(e.code))
+}
+
+/// Returns `true` if a key is a RON identifier, raw ones included.
+#[cfg(feature = "ron")]
+fn is_ron_identifier(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '+' | '-'))
+}
+
+/// Returns `true` if a RON identifier doesn't have to be raw.
+#[cfg(feature = "ron")]
+fn is_plain_ron_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !matches!(
+            key,
+            "true"
+                | "false"
+                | "Some"
+                | "None"
+                | "inf"
+                | "inff32"
+                | "inff64"
+                | "NaN"
+                | "NaNf32"
+                | "NaNf64"
+        )
+}
+
+/// Adapts a value to the target format, reporting unrepresentable values by
+/// their paths.
+fn prepare(value: Value, format: FrontmatterFormat, path: &str) -> Result<Value, Error> {
+    let unrepresentable = |kind| Err(Error::Unrepresentable(kind, path.into(), format.into()));
+    Ok(match (value, format) {
+        (Value::Null, FrontmatterFormat::Toml) => return unrepresentable("null"),
+        (Value::Float(f), FrontmatterFormat::Json | FrontmatterFormat::Json5) if !f.is_finite() => {
+            return unrepresentable("non-finite float");
+        }
+        (Value::Datetime(_), FrontmatterFormat::Json | FrontmatterFormat::Json5) => {
+            return unrepresentable("datetime");
+        }
+        (Value::Datetime(datetime), FrontmatterFormat::Yaml | FrontmatterFormat::Ron) => {
+            Value::String(datetime)
+        }
+        (
+            Value::Tagged(..),
+            FrontmatterFormat::Json
+            | FrontmatterFormat::Json5
+            | FrontmatterFormat::Toml
+            | FrontmatterFormat::Ron,
+        ) => {
+            return unrepresentable("tagged value");
+        }
+        (Value::Tagged(tag, value), _) => {
+            Value::Tagged(tag, Box::new(prepare(*value, format, path)?))
+        }
+        (Value::Array(items), _) => Value::Array(
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| prepare(item, format, &format!("{path}[{i}]")))
+                .collect::<Result<_, _>>()?,
+        ),
+        (Value::Table(table), _) => Value::Table(
+            table
+                .into_iter()
+                .map(|(key, value)| {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    Ok((key, prepare(value, format, &path)?))
+                })
+                .collect::<Result<_, Error>>()?,
+        ),
+        (value, _) => value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn without_frontmatter() {
+        let doc = "\n\nhello world";
+        assert_eq!(convert(doc, FrontmatterFormat::Yaml).unwrap(), doc);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn same_format() {
+        let doc = "---\n# comment\nfoo: bar\n---\nhello world";
+        assert_eq!(convert(doc, FrontmatterFormat::Yaml).unwrap(), doc);
+    }
+
+    #[cfg(all(feature = "toml", feature = "yaml"))]
+    mod yaml_toml {
+        use super::*;
+
+        #[test]
+        fn keeps_key_order() {
+            let doc = "---\nz: 1\nextra:\n  b: true\n  a: 1.5\nlist:\n- x\n---\n\n# Body\r\n";
+            assert_eq!(
+                convert(doc, FrontmatterFormat::Toml).unwrap(),
+                "+++\nz = 1\nlist = [\"x\"]\n\n[extra]\nb = true\na = 1.5\n+++\n\n# Body\r\n"
+            );
+        }
+
+        #[test]
+        fn empty_yaml() {
+            let doc = "---\n---\nbody";
+            assert_eq!(
+                convert(doc, FrontmatterFormat::Toml).unwrap(),
+                "+++\n+++\nbody"
+            );
+        }
+
+        #[test]
+        fn null_is_unrepresentable() {
+            let doc = "---\nauthors:\n- name: a\n- name: b\n  email: ~\n---\n";
+            let err = convert(doc, FrontmatterFormat::Toml).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "null at `authors[1].email` can't be represented in TOML"
+            );
+        }
+
+        #[test]
+        fn tag_is_unrepresentable() {
+            let doc = "---\nfoo: !bar baz\n---\n";
+            assert!(matches!(
+                convert(doc, FrontmatterFormat::Toml).unwrap_err(),
+                Error::Unrepresentable("tagged value", path, "TOML") if path == "foo"
+            ));
+        }
+
+        #[test]
+        fn datetime_into_yaml() {
+            let doc = "+++\ndate = 1979-05-27T07:32:00Z\n+++\nbody";
+            assert_eq!(
+                convert(doc, FrontmatterFormat::Yaml).unwrap(),
+                "---\ndate: 1979-05-27T07:32:00Z\n---\nbody"
+            );
+        }
+    }
+
+    #[cfg(all(feature = "json", feature = "toml"))]
+    mod json_toml {
+        use super::*;
+
+        #[test]
+        fn toml_into_json() {
+            let doc = "+++\ntitle = \"Hello\"\n+++\nbody";
+            assert_eq!(
+                convert(doc, FrontmatterFormat::Json).unwrap(),
+                "{\n  \"title\": \"Hello\"\n}\nbody"
+            );
+        }
+
+        #[test]
+        fn datetime_is_unrepresentable() {
+            let doc = "+++\n[post]\ndate = 1979-05-27\n+++\n";
+            assert!(matches!(
+                convert(doc, FrontmatterFormat::Json).unwrap_err(),
+                Error::Unrepresentable("datetime", path, "JSON") if path == "post.date"
+            ));
+        }
+
+        #[test]
+        fn non_finite_float_is_unrepresentable() {
+            let doc = "+++\nfoo = [1.0, nan]\n+++\n";
+            assert!(matches!(
+                convert(doc, FrontmatterFormat::Json).unwrap_err(),
+                Error::Unrepresentable("non-finite float", path, "JSON") if path == "foo[1]"
+            ));
+        }
+    }
+
+    #[cfg(all(feature = "ron", feature = "yaml"))]
+    mod yaml_ron {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let doc = "---\ntitle: Hello\nfoo-bar: 1.5\ntags:\n- a\nextra:\n  b: true\nnone: null\n---\nbody";
+            let ron = convert(doc, FrontmatterFormat::Ron).unwrap();
+            assert_eq!(
+                ron,
+                "(\n    title: \"Hello\",\n    r#foo-bar: 1.5,\n    tags: [\n        \"a\",\n    ],\n    \
+                 extra: {\n        \"b\": true,\n    },\n    none: (),\n)\nbody"
+            );
+            assert_eq!(convert(&ron, FrontmatterFormat::Yaml).unwrap(), doc);
+        }
+
+        #[test]
+        fn empty_yaml() {
+            let doc = "---\n---\nbody";
+            assert_eq!(convert(doc, FrontmatterFormat::Ron).unwrap(), "(\n)\nbody");
+        }
+
+        #[test]
+        fn key_is_unrepresentable() {
+            let doc = "---\nmy title: Hello\n---\n";
+            assert!(matches!(
+                convert(doc, FrontmatterFormat::Ron).unwrap_err(),
+                Error::Unrepresentable("key", path, "RON") if path == "my title"
+            ));
+        }
+    }
+}
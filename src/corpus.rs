@@ -0,0 +1,191 @@
+//! Loading a directory of documents, see [`load`].
+
+use std::{
+    fs,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::{Error, Parser};
+
+/// A document loaded by [`load`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document<T> {
+    /// Path of the document, joined to the root.
+    pub path: PathBuf,
+    /// Parsed frontmatter of the document.
+    pub frontmatter: T,
+    /// Body of the document.
+    pub body: String,
+}
+
+/// Documents loaded by [`load`] along with the files that failed to load.
+#[derive(Debug)]
+pub struct Corpus<T> {
+    /// Loaded documents, sorted by path.
+    pub documents: Vec<Document<T>>,
+    /// Errors of reading directories and reading or parsing documents, sorted
+    /// by path.
+    pub errors: Vec<(PathBuf, Error)>,
+}
+
+/// Recursively loads `*.md` files under a root directory, parsing their
+/// frontmatter in parallel.
+///
+/// A bad file doesn't abort the loading, its error is collected into
+/// [`Corpus::errors`] instead. Symbolic links to directories aren't followed.
+pub fn load<T: serde::de::DeserializeOwned + Send>(root: impl AsRef<Path>) -> Corpus<T> {
+    Parser::new().load_corpus(root)
+}
+
+impl Parser {
+    /// Loads a directory of documents like [`corpus::load`](load).
+    pub fn load_corpus<T: serde::de::DeserializeOwned + Send>(
+        &self,
+        root: impl AsRef<Path>,
+    ) -> Corpus<T> {
+        let mut paths = Vec::new();
+        let mut errors = Vec::new();
+        walk(root.as_ref(), &mut paths, &mut errors);
+
+        let workers = thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(paths.len());
+        let next = AtomicUsize::new(0);
+        let results = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut results = Vec::new();
+                        while let Some(path) = paths.get(next.fetch_add(1, Ordering::Relaxed)) {
+                            results.push((path, self.load_document(path)));
+                        }
+                        results
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut documents = Vec::new();
+        for (path, result) in results {
+            match result {
+                Ok((frontmatter, body)) => documents.push(Document {
+                    path: path.clone(),
+                    frontmatter,
+                    body,
+                }),
+                Err(e) => errors.push((path.clone(), e)),
+            }
+        }
+        documents.sort_by(|a, b| a.path.cmp(&b.path));
+        errors.sort_by(|a, b| a.0.cmp(&b.0));
+        Corpus { documents, errors }
+    }
+
+    fn load_document<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &Path,
+    ) -> Result<(T, String), Error> {
+        let content = fs::read_to_string(path).map_err(Error::Io)?;
+        let (frontmatter, body) = self.parse(&content)?;
+        Ok((frontmatter, body.to_owned()))
+    }
+}
+
+/// Collects paths of `*.md` files under a directory.
+pub(crate) fn walk(dir: &Path, paths: &mut Vec<PathBuf>, errors: &mut Vec<(PathBuf, Error)>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => return errors.push((dir.to_owned(), Error::Io(e))),
+    };
+    for entry in entries {
+        let entry = match entry.and_then(|entry| Ok((entry.path(), entry.file_type()?))) {
+            Ok(entry) => entry,
+            Err(e) => {
+                errors.push((dir.to_owned(), Error::Io(e)));
+                continue;
+            }
+        };
+        match entry {
+            (path, file_type) if file_type.is_dir() => walk(&path, paths, errors),
+            (path, _) if path.extension().is_some_and(|ext| ext == "md") => paths.push(path),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "yaml"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Frontmatter {
+        title: String,
+    }
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn loads_nested_documents() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        write(root, "b.md", "---\ntitle: B\n---\nWorld");
+        write(root, "a/c.md", "---\ntitle: C\n---\n");
+        write(root, "a/d.txt", "---\ntitle: D\n---\n");
+        write(root, "a/e/f.md", "---\ntitle: [\n---\n");
+        write(root, "g.md", "no frontmatter");
+
+        let corpus = load::<Frontmatter>(root);
+        let documents: Vec<_> = corpus
+            .documents
+            .iter()
+            .map(|doc| {
+                (
+                    doc.path.strip_prefix(root).unwrap(),
+                    doc.frontmatter.title.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            documents,
+            [(Path::new("a/c.md"), "C"), (Path::new("b.md"), "B")]
+        );
+        assert_eq!(corpus.documents[1].body, "World");
+
+        let errors: Vec<_> = corpus
+            .errors
+            .iter()
+            .map(|(path, _)| path.strip_prefix(root).unwrap())
+            .collect();
+        assert_eq!(errors, [Path::new("a/e/f.md"), Path::new("g.md")]);
+        assert!(matches!(corpus.errors[0].1, Error::InvalidYaml(..)));
+    }
+
+    #[test]
+    fn missing_root() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().join("missing");
+        let corpus = load::<Frontmatter>(&root);
+        assert!(corpus.documents.is_empty());
+        assert!(matches!(
+            &corpus.errors[..],
+            [(path, Error::Io(e))] if *path == root && e.kind() == io::ErrorKind::NotFound
+        ));
+    }
+}
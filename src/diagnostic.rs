@@ -0,0 +1,298 @@
+use std::fmt::Display;
+
+use miette::{Diagnostic, LabeledSpan};
+
+use crate::{Error, Span};
+
+/// Errors carry spans, but not the document itself, so attach it to a report
+/// to render the offending snippet:
+///
+/// ```
+/// let doc = "---\ntitle: Hello\n";
+///
+/// let err = markdown_frontmatter::split(doc).unwrap_err();
+/// let report = miette::Report::new(err).with_source_code(doc);
+/// ```
+impl Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = match self {
+            Self::DisabledFormat(..) => "disabled_format",
+            Self::AbsentClosingDelimiter(..) => "absent_closing_delimiter",
+            Self::AbsentFrontmatter => "absent_frontmatter",
+            #[cfg(all(feature = "edit", any(feature = "toml", feature = "yaml")))]
+            Self::NotEditable(_) => "not_editable",
+            Self::NotSerializable(_) => "not_serializable",
+            #[cfg(feature = "json")]
+            Self::InvalidJson(..) => "invalid_json",
+            #[cfg(feature = "json5")]
+            Self::InvalidJson5(..) => "invalid_json5",
+            #[cfg(feature = "toml")]
+            Self::InvalidToml(..) => "invalid_toml",
+            #[cfg(feature = "yaml")]
+            Self::InvalidYaml(..) => "invalid_yaml",
+            #[cfg(feature = "ron")]
+            Self::InvalidRon(..) => "invalid_ron",
+            #[cfg(feature = "_serde")]
+            Self::Invalid(..) => "invalid",
+            #[cfg(feature = "json")]
+            Self::DeserializeJson(..) => "deserialize_json",
+            #[cfg(feature = "json5")]
+            Self::DeserializeJson5(..) => "deserialize_json5",
+            #[cfg(feature = "toml")]
+            Self::DeserializeToml(..) => "deserialize_toml",
+            #[cfg(feature = "yaml")]
+            Self::DeserializeYaml(..) => "deserialize_yaml",
+            #[cfg(feature = "ron")]
+            Self::DeserializeRon(..) => "deserialize_ron",
+            #[cfg(feature = "_serde")]
+            Self::Deserialize(..) => "deserialize",
+            #[cfg(feature = "json")]
+            Self::SerializeJson(_) => "serialize_json",
+            #[cfg(feature = "toml")]
+            Self::SerializeToml(_) => "serialize_toml",
+            #[cfg(feature = "yaml")]
+            Self::SerializeYaml(_) => "serialize_yaml",
+            #[cfg(feature = "ron")]
+            Self::SerializeRon(_) => "serialize_ron",
+            Self::Unrepresentable(..) => "unrepresentable",
+            Self::DelimiterInFrontmatter(_) => "delimiter_in_frontmatter",
+            Self::Io(_) => "io",
+            #[cfg(feature = "watch")]
+            Self::Watch(_) => "watch",
+        };
+        Some(Box::new(format!("markdown_frontmatter::{code}")))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            Self::DisabledFormat(format, _) => Some(Box::new(format!(
+                "enable the `{}` cargo feature",
+                format.to_lowercase()
+            ))),
+            Self::AbsentClosingDelimiter(_, _, closing) if closing.is_empty() => {
+                Some(Box::new("end the frontmatter with a blank line"))
+            }
+            Self::AbsentClosingDelimiter(_, _, closing) => Some(Box::new(format!(
+                "close the frontmatter with a `{closing}` line"
+            ))),
+            _ => None,
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let (label, span) = match self {
+            Self::DisabledFormat(_, Some(span)) => {
+                ("frontmatter of a disabled format".into(), span)
+            }
+            Self::AbsentClosingDelimiter(_, span, _) => {
+                ("opening delimiter without a closing one".into(), span)
+            }
+            #[cfg(feature = "json")]
+            Self::InvalidJson(e, span) => (json_message(e), span),
+            #[cfg(feature = "json5")]
+            Self::InvalidJson5(e, span) => (json5_message(e), span),
+            #[cfg(feature = "toml")]
+            Self::InvalidToml(e, span) => (e.message().trim_end().into(), span),
+            #[cfg(feature = "yaml")]
+            Self::InvalidYaml(e, span) => (yaml_message(e), span),
+            #[cfg(feature = "ron")]
+            Self::InvalidRon(e, span) => (e.code.to_string(), span),
+            #[cfg(feature = "_serde")]
+            Self::Invalid(_, e, span) => (e.to_string(), span),
+            #[cfg(feature = "json")]
+            Self::DeserializeJson(e, span, path) => (format!("{path}: {}", json_message(e)), span),
+            #[cfg(feature = "json5")]
+            Self::DeserializeJson5(e, span, path) => (format!("{path}: {e}"), span),
+            #[cfg(feature = "toml")]
+            Self::DeserializeToml(e, span, path) => {
+                (format!("{path}: {}", e.message().trim_end()), span)
+            }
+            #[cfg(feature = "yaml")]
+            Self::DeserializeYaml(e, span, path) => (format!("{path}: {}", yaml_message(e)), span),
+            #[cfg(feature = "ron")]
+            Self::DeserializeRon(e, span, path) => (format!("{path}: {}", e.code), span),
+            #[cfg(feature = "_serde")]
+            Self::Deserialize(_, e, span, path) => (format!("{path}: {e}"), span),
+            _ => return None,
+        };
+        Some(Box::new(std::iter::once(labeled(label, *span))))
+    }
+}
+
+fn labeled(label: String, span: Span) -> LabeledSpan {
+    LabeledSpan::new_primary_with_span(Some(label), span.range())
+}
+
+/// Error message without the location suffix, the location is relative to the
+/// frontmatter, so it would be confusing in a report of the whole document.
+#[cfg(feature = "json")]
+fn json_message(e: &serde_json::Error) -> String {
+    let message = e.to_string();
+    let suffix = format!(" at line {} column {}", e.line(), e.column());
+    message
+        .strip_suffix(&suffix)
+        .map_or_else(|| message.clone(), Into::into)
+}
+
+/// Error message without the snippet, syntax errors are rendered with the
+/// offending line followed by `= expected ...`.
+#[cfg(feature = "json5")]
+fn json5_message(e: &json5::Error) -> String {
+    let message = e.to_string();
+    message
+        .lines()
+        .last()
+        .and_then(|line| line.trim_start().strip_prefix("= "))
+        .map_or_else(|| message.clone(), Into::into)
+}
+
+/// Error message without the location suffix, see [`json_message`].
+#[cfg(feature = "yaml")]
+fn yaml_message(e: &serde_yaml::Error) -> String {
+    let message = e.to_string();
+    let Some(location) = e.location() else {
+        return message;
+    };
+    let suffix = format!(" at line {} column {}", location.line(), location.column());
+    message
+        .strip_suffix(&suffix)
+        .map_or_else(|| message.clone(), Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::{NarratableReportHandler, Report};
+
+    use super::*;
+
+    fn render(err: Error, doc: &'static str) -> String {
+        let report = Report::new(err).with_source_code(doc);
+        let mut out = String::new();
+        NarratableReportHandler::new()
+            .render_report(&mut out, report.as_ref())
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn absent_closing_delimiter() {
+        let doc = "\n---\ntitle: Hello\n";
+        let err = crate::split(doc).unwrap_err();
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(&doc[label.offset()..label.offset() + label.len()], "---");
+        assert_eq!(
+            err.help().unwrap().to_string(),
+            "close the frontmatter with a `---` line"
+        );
+        let out = render(err, doc);
+        assert!(
+            out.contains("opening delimiter without a closing one"),
+            "{out}"
+        );
+        assert!(out.contains("markdown_frontmatter::absent_closing_delimiter"));
+    }
+
+    #[test]
+    fn closing_delimiter_of_frame() {
+        let parser = crate::Parser::new().html_comment(true).fenced_code(true);
+        for (doc, closing) in [
+            ("---toml\ntitle = 1\n", "---"),
+            ("```yaml\ntitle: Hello\n", "```"),
+            ("<!--\n---\ntitle: Hello\n---\n", "-->"),
+        ] {
+            let err = parser.split(doc).unwrap_err();
+            assert_eq!(
+                err.help().unwrap().to_string(),
+                format!("close the frontmatter with a `{closing}` line")
+            );
+        }
+    }
+
+    #[test]
+    fn closing_delimiter_of_registered_delimiters() {
+        let parser = crate::Parser::new().delimiters(crate::FrontmatterFormat::Json, ";;;", ";;;");
+        let doc = ";;;\n\"title\": \"Hello\"\n";
+        let err = parser.split(doc).unwrap_err();
+        assert_eq!(
+            err.help().unwrap().to_string(),
+            "close the frontmatter with a `;;;` line"
+        );
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(&doc[label.offset()..label.offset() + label.len()], ";;;");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn invalid_yaml() {
+        let doc = "---\ntitle: Hello: World\n---\n";
+        let err = crate::parse::<serde_yaml::Value>(doc).unwrap_err();
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(label.offset(), 16);
+        assert_eq!(
+            label.label(),
+            Some("mapping values are not allowed in this context")
+        );
+        let out = render(err, doc);
+        assert!(out.contains("title: Hello: World"), "{out}");
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn invalid_toml_type() {
+        #[derive(Debug, serde::Deserialize)]
+        #[expect(dead_code)]
+        struct Frontmatter {
+            title: String,
+        }
+
+        let doc = "+++\ntitle = 1\n+++\n";
+        let err = crate::parse::<Frontmatter>(doc).unwrap_err();
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(&doc[label.offset()..label.offset() + label.len()], "1");
+        assert!(label.label().unwrap().contains("expected a string"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn invalid_json() {
+        let doc = "{\n  \"title\": @\n}\n";
+        let err = crate::parse::<serde_json::Value>(doc).unwrap_err();
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(label.offset(), 13);
+        assert_eq!(label.label(), Some("expected value"));
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn invalid_ron() {
+        let doc = "(\n    title: \"Hello\" 1,\n)\n";
+        let err = crate::parse::<ron::Value>(doc).unwrap_err();
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(&doc[label.offset()..label.offset() + label.len()], "1");
+    }
+
+    #[cfg(feature = "json5")]
+    #[test]
+    fn invalid_json5() {
+        let doc = "{\n  title: @,\n}\n";
+        let err = crate::Parser::new()
+            .json5(true)
+            .parse::<serde_json::Value>(doc)
+            .unwrap_err();
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(&doc[label.offset()..label.offset() + label.len()], "@");
+        assert!(label.label().unwrap().starts_with("expected "), "{label:?}");
+    }
+
+    #[test]
+    fn no_labels_without_span() {
+        assert!(Error::AbsentFrontmatter.labels().is_none());
+        assert_eq!(
+            Error::DisabledFormat("YAML", None)
+                .help()
+                .unwrap()
+                .to_string(),
+            "enable the `yaml` cargo feature"
+        );
+    }
+}
@@ -0,0 +1,605 @@
+use std::fmt;
+
+use crate::{Error, FrontmatterFormat, LineSpan, SplitFrontmatter, split};
+
+/// Format-preserving editor of a document frontmatter.
+///
+/// Unlike parsing and serializing the frontmatter back, the editor only
+/// touches the keys being changed: comments, whitespace, key order and quoting
+/// of the rest of the frontmatter are preserved, and the body is kept
+/// byte-identical. Only top-level keys of TOML and YAML frontmatter can be
+/// edited.
+///
+/// # Examples
+///
+/// ```
+/// use markdown_frontmatter::Editor;
+///
+/// let doc = "+++\n# Post metadata\ntitle = 'Hello'  # keep quotes\nupdated = 2024-01-01\n+++\nWorld";
+///
+/// let mut editor = Editor::new(doc).unwrap();
+/// assert_eq!(editor.get::<String>("title").unwrap().unwrap(), "Hello");
+/// editor.set("updated", "2024-02-02").unwrap();
+/// assert_eq!(
+///     editor.to_string(),
+///     "+++\n# Post metadata\ntitle = 'Hello'  # keep quotes\nupdated = \"2024-02-02\"\n+++\nWorld"
+/// );
+/// ```
+pub struct Editor<'a> {
+    content: &'a str,
+    frontmatter: SplitFrontmatter<'a>,
+    /// The line closing the frontmatter in the document.
+    closing: &'a str,
+    matter: Matter,
+}
+
+enum Matter {
+    #[cfg(feature = "toml")]
+    Toml(toml_edit::DocumentMut),
+    #[cfg(feature = "yaml")]
+    Yaml(String),
+}
+
+impl<'a> Editor<'a> {
+    /// Creates an editor of the document frontmatter.
+    ///
+    /// Returns an error if the document has no frontmatter or its format
+    /// doesn't support editing.
+    pub fn new(content: &'a str) -> Result<Self, Error> {
+        let (frontmatter, _) = split(content)?;
+        let frontmatter = frontmatter.ok_or(Error::AbsentFrontmatter)?;
+        let locate = |range| frontmatter.locate(content, range);
+        let matter = match frontmatter.format() {
+            #[cfg(feature = "toml")]
+            FrontmatterFormat::Toml => Matter::Toml(toml::parse(frontmatter.as_str(), locate)?),
+            #[cfg(feature = "yaml")]
+            FrontmatterFormat::Yaml => Matter::Yaml(yaml::parse(frontmatter.as_str(), locate)?),
+            format => return Err(Error::NotEditable(format.into())),
+        };
+        let closing = LineSpan::new(&content[frontmatter.span().end..])
+            .next()
+            .map_or("", |span| span.line);
+        // The closing line of a frontmatter wrapped in an HTML comment may
+        // close the comment as well
+        let closing = closing.strip_suffix("-->").unwrap_or(closing).trim_end();
+        Ok(Self {
+            content,
+            frontmatter,
+            closing,
+            matter,
+        })
+    }
+
+    /// Returns the format of the frontmatter.
+    pub fn format(&self) -> FrontmatterFormat {
+        match self.matter {
+            #[cfg(feature = "toml")]
+            Matter::Toml(_) => FrontmatterFormat::Toml,
+            #[cfg(feature = "yaml")]
+            Matter::Yaml(_) => FrontmatterFormat::Yaml,
+        }
+    }
+
+    /// Deserializes the value of a top-level key, returns `None` if the key is
+    /// absent.
+    ///
+    /// As the frontmatter may be already edited, errors point to the whole
+    /// original frontmatter.
+    pub fn get<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<Option<T>, Error> {
+        let span = self.frontmatter.locate(self.content, None);
+        match &self.matter {
+            #[cfg(feature = "toml")]
+            Matter::Toml(doc) => toml::get(doc, key, span),
+            #[cfg(feature = "yaml")]
+            Matter::Yaml(matter) => yaml::get(matter, key, span),
+        }
+    }
+
+    /// Sets the value of a top-level key.
+    ///
+    /// An existing key is replaced in place, a new one is appended to the
+    /// top-level keys. Fails with [`Error::DelimiterInFrontmatter`] if the
+    /// serialized value contains the closing line of the frontmatter.
+    pub fn set<T: serde::Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        let format = self.format();
+        let check = |serialized: &str| {
+            if LineSpan::new(serialized).any(|span| span.line.trim_end() == self.closing) {
+                return Err(Error::DelimiterInFrontmatter(format.into()));
+            }
+            Ok(())
+        };
+        match &mut self.matter {
+            #[cfg(feature = "toml")]
+            Matter::Toml(doc) => toml::set(doc, key, value, check),
+            #[cfg(feature = "yaml")]
+            Matter::Yaml(matter) => yaml::set(matter, key, value, check),
+        }
+    }
+
+    /// Removes a top-level key, returns `false` if the key is absent.
+    pub fn remove(&mut self, key: &str) -> bool {
+        match &mut self.matter {
+            #[cfg(feature = "toml")]
+            Matter::Toml(doc) => doc.remove(key).is_some(),
+            #[cfg(feature = "yaml")]
+            Matter::Yaml(matter) => yaml::remove(matter, key),
+        }
+    }
+}
+
+impl fmt::Display for Editor<'_> {
+    /// Writes the edited document.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.frontmatter.span();
+        f.write_str(&self.content[..span.start])?;
+        match &self.matter {
+            #[cfg(feature = "toml")]
+            Matter::Toml(doc) => write!(f, "{doc}")?,
+            #[cfg(feature = "yaml")]
+            Matter::Yaml(matter) => f.write_str(matter)?,
+        }
+        f.write_str(&self.content[span.end..])
+    }
+}
+
+#[cfg(feature = "toml")]
+mod toml {
+    use std::ops::Range;
+
+    use toml_edit::{DocumentMut, Item, Value};
+
+    use crate::{Error, Span, deserialize};
+
+    pub(super) fn parse(
+        matter: &str,
+        locate: impl Fn(Option<Range<usize>>) -> Span,
+    ) -> Result<DocumentMut, Error> {
+        matter.parse().map_err(|_| {
+            // Reparse to report the error the same way as parsing does
+            match toml::from_str::<toml::Table>(matter) {
+                Err(e) => {
+                    let span = locate(e.span());
+                    Error::InvalidToml(e, span)
+                }
+                Ok(_) => Error::NotEditable(crate::FrontmatterFormat::Toml.into()),
+            }
+        })
+    }
+
+    pub(super) fn get<T: serde::de::DeserializeOwned>(
+        doc: &DocumentMut,
+        key: &str,
+        span: Span,
+    ) -> Result<Option<T>, Error> {
+        let Some(Ok(mut value)) = doc.get(key).cloned().map(Item::into_value) else {
+            return Ok(None);
+        };
+        value.decor_mut().clear();
+        let raw = value.to_string();
+        let deserializer = toml::de::ValueDeserializer::parse(&raw)
+            .map_err(|e| Error::DeserializeToml(Box::new(e), span, key.into()))?;
+        deserialize(deserializer, key)
+            .map(Some)
+            .map_err(|(e, path)| Error::DeserializeToml(Box::new(e), span, path))
+    }
+
+    pub(super) fn set<T: serde::Serialize + ?Sized>(
+        doc: &mut DocumentMut,
+        key: &str,
+        value: &T,
+        check: impl Fn(&str) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let mut raw = String::new();
+        value
+            .serialize(toml::ser::ValueSerializer::new(&mut raw))
+            .map_err(Error::SerializeToml)?;
+        check(&raw)?;
+        let mut value: Value = raw
+            .parse()
+            .map_err(|e| Error::SerializeToml(serde::ser::Error::custom(e)))?;
+
+        match doc.get_mut(key) {
+            Some(Item::Value(old)) => {
+                *value.decor_mut() = old.decor().clone();
+                *old = value;
+            }
+            // Keep the shape of standard tables and arrays of tables if possible
+            Some(item @ Item::Table(_)) => {
+                *item = Item::Value(value)
+                    .into_table()
+                    .map_or_else(|item| item, Item::Table);
+            }
+            Some(item @ Item::ArrayOfTables(_)) => {
+                *item = Item::Value(value)
+                    .into_array_of_tables()
+                    .map_or_else(|item| item, Item::ArrayOfTables);
+            }
+            Some(item @ Item::None) => *item = Item::Value(value),
+            None => {
+                doc.insert(key, Item::Value(value));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    use std::ops::Range;
+
+    use serde_yaml::{Mapping, Value};
+
+    use crate::{Error, FrontmatterFormat, LineSpan, Span, char_range, deserialize};
+
+    pub(super) fn parse(
+        matter: &str,
+        locate: impl Fn(Option<Range<usize>>) -> Span,
+    ) -> Result<String, Error> {
+        let not_editable = || Error::NotEditable(FrontmatterFormat::Yaml.into());
+        let value = serde_yaml::from_str(matter).map_err(|e| {
+            let span = locate(e.location().map(|l| char_range(matter, l.index())));
+            Error::InvalidYaml(e, span)
+        })?;
+        let mapping = match value {
+            Value::Mapping(mapping) => mapping,
+            Value::Null => Mapping::new(),
+            _ => return Err(not_editable()),
+        };
+
+        let matter = if mapping.is_empty() {
+            // Drop flow-style or null placeholders like `{}` keeping comments
+            LineSpan::new(matter)
+                .filter(|span| !matches!(span.line.trim(), "{}" | "null" | "~"))
+                .map(|span| &matter[span.start..span.next_start])
+                .collect()
+        } else {
+            matter.to_owned()
+        };
+        entries(&matter).ok_or_else(not_editable)?;
+        Ok(matter)
+    }
+
+    pub(super) fn get<T: serde::de::DeserializeOwned>(
+        matter: &str,
+        key: &str,
+        span: Span,
+    ) -> Result<Option<T>, Error> {
+        let mapping: Option<Mapping> =
+            serde_yaml::from_str(matter).map_err(|e| Error::InvalidYaml(e, span))?;
+        let Some(value) = mapping.and_then(|mut mapping| mapping.remove(key)) else {
+            return Ok(None);
+        };
+        deserialize(value, key)
+            .map(Some)
+            .map_err(|(e, path)| Error::DeserializeYaml(e, span, path))
+    }
+
+    pub(super) fn set<T: serde::Serialize + ?Sized>(
+        matter: &mut String,
+        key: &str,
+        value: &T,
+        check: impl Fn(&str) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let value = serde_yaml::to_value(value).map_err(Error::SerializeYaml)?;
+        let mut mapping = Mapping::new();
+        mapping.insert(key.into(), value);
+        let entry = serde_yaml::to_string(&mapping).map_err(Error::SerializeYaml)?;
+        check(&entry)?;
+
+        if let Some(range) = find(matter, key) {
+            matter.replace_range(range, &entry);
+        } else {
+            if !matter.is_empty() && !matter.ends_with(['\n', '\r']) {
+                matter.push('\n');
+            }
+            matter.push_str(&entry);
+        }
+        Ok(())
+    }
+
+    pub(super) fn remove(matter: &mut String, key: &str) -> bool {
+        let Some(range) = find(matter, key) else {
+            return false;
+        };
+        matter.replace_range(range, "");
+        true
+    }
+
+    fn find(matter: &str, key: &str) -> Option<Range<usize>> {
+        entries(matter)?
+            .into_iter()
+            .find_map(|(entry_key, range)| (entry_key == key).then_some(range))
+    }
+
+    /// Returns top-level keys of a block mapping with the byte ranges of their
+    /// entries, including nested lines, or `None` if the mapping isn't
+    /// block-style or is indented.
+    fn entries(matter: &str) -> Option<Vec<(String, Range<usize>)>> {
+        let mut entries: Vec<(String, Range<usize>)> = Vec::new();
+        let mut in_entry = false;
+        for span in LineSpan::new(matter) {
+            let line = span.line;
+            if line.starts_with([' ', '\t']) || line.is_empty() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() || !in_entry && trimmed.starts_with('#') {
+                    continue;
+                }
+                // An indented line outside an entry is an indented mapping
+                if !in_entry {
+                    return None;
+                }
+                // Nested line, trailing blank lines aren't a part of the entry
+                if let Some((_, range)) = entries.last_mut() {
+                    range.end = span.next_start;
+                }
+            } else if line.starts_with('#') {
+                in_entry = false;
+            } else {
+                entries.push((key(line)?, span.start..span.next_start));
+                in_entry = true;
+            }
+        }
+        Some(entries)
+    }
+
+    /// Parses the key of a top-level entry line.
+    fn key(line: &str) -> Option<String> {
+        let (key, rest) = if line.starts_with(['"', '\'']) {
+            let end = quoted_end(line)?;
+            let key = serde_yaml::from_str(&line[..end]).ok()?;
+            (key, &line[end..])
+        } else if line.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '%', '@', '`',
+        ]) {
+            return None;
+        } else {
+            let colon = line
+                .match_indices(':')
+                .map(|(i, _)| i)
+                .find(|&i| line[i + 1..].is_empty() || line[i + 1..].starts_with([' ', '\t']))?;
+            (line[..colon].trim_end().to_owned(), &line[colon..])
+        };
+        let rest = rest.trim_start();
+        (rest.starts_with(':') && (rest.len() == 1 || rest[1..].starts_with([' ', '\t'])))
+            .then_some(key)
+    }
+
+    /// Returns the end of a quoted scalar at the start of a line.
+    fn quoted_end(line: &str) -> Option<usize> {
+        let quote = line.chars().next()?;
+        let mut chars = line.char_indices().skip(1);
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' if quote == '"' => {
+                    chars.next();
+                }
+                // Single quotes are escaped by doubling them
+                '\'' if quote == '\'' && line[i + 1..].starts_with('\'') => {
+                    chars.next();
+                }
+                c if c == quote => return Some(i + 1),
+                _ => (),
+            }
+        }
+        None
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn entry_keys() {
+            assert_eq!(key("foo: bar").as_deref(), Some("foo"));
+            assert_eq!(key("foo:").as_deref(), Some("foo"));
+            assert_eq!(key("foo bar : 1").as_deref(), Some("foo bar"));
+            assert_eq!(key("url: http://x").as_deref(), Some("url"));
+            assert_eq!(key("a:b: c").as_deref(), Some("a:b"));
+            assert_eq!(key("\"a: b\": c").as_deref(), Some("a: b"));
+            assert_eq!(key("'it''s': c").as_deref(), Some("it's"));
+            assert_eq!(key("- foo"), None);
+            assert_eq!(key("{a: 1}"), None);
+            assert_eq!(key("plain"), None);
+        }
+
+        #[test]
+        fn entry_ranges() {
+            let matter = "a: 1\nb:\n  - x\n\n  - y\n\n# c\nc: |\n  text\n";
+            let entries = entries(matter).unwrap();
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|(key, range)| (key.as_str(), &matter[range.clone()]))
+                .collect();
+            assert_eq!(
+                entries,
+                [
+                    ("a", "a: 1\n"),
+                    ("b", "b:\n  - x\n\n  - y\n"),
+                    ("c", "c: |\n  text\n")
+                ]
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absent_frontmatter() {
+        let result = Editor::new("hello world");
+        assert!(matches!(result.err().unwrap(), Error::AbsentFrontmatter));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_not_editable() {
+        let result = Editor::new("{\n\"a\": 1\n}\n");
+        assert!(matches!(result.err().unwrap(), Error::NotEditable("JSON")));
+    }
+
+    #[cfg(feature = "toml")]
+    mod toml {
+        use super::*;
+
+        const DOC: &str = "\n+++\n# comment\ntitle = 'Hello'  # trailing\ntags = [\"a\",   \"b\"]\n\n[extra]\nx = 1\n+++\n  body\r\n+++\n";
+
+        #[test]
+        fn get() {
+            let editor = Editor::new(DOC).unwrap();
+            assert_eq!(editor.format(), FrontmatterFormat::Toml);
+            assert_eq!(editor.get::<String>("title").unwrap().unwrap(), "Hello");
+            assert_eq!(
+                editor.get::<Vec<String>>("tags").unwrap().unwrap(),
+                ["a", "b"]
+            );
+            assert_eq!(
+                editor
+                    .get::<std::collections::BTreeMap<String, i64>>("extra")
+                    .unwrap()
+                    .unwrap()["x"],
+                1
+            );
+            assert!(editor.get::<String>("absent").unwrap().is_none());
+            assert!(matches!(
+                editor.get::<i64>("title").unwrap_err(),
+                Error::DeserializeToml(..)
+            ));
+            let err = editor
+                .get::<std::collections::BTreeMap<String, String>>("extra")
+                .unwrap_err();
+            assert_eq!(err.path(), Some("extra.x"));
+        }
+
+        #[test]
+        fn set_existing() {
+            let mut editor = Editor::new(DOC).unwrap();
+            editor.set("title", "Bye").unwrap();
+            assert_eq!(editor.to_string(), DOC.replace("'Hello'", "\"Bye\""));
+        }
+
+        #[test]
+        fn set_new() {
+            let mut editor = Editor::new(DOC).unwrap();
+            editor.set("draft", &true).unwrap();
+            assert_eq!(
+                editor.to_string(),
+                DOC.replace("\n\n[extra]", "\ndraft = true\n\n[extra]")
+            );
+        }
+
+        #[test]
+        fn set_closing_delimiter() {
+            let mut editor = Editor::new(DOC).unwrap();
+            assert!(matches!(
+                editor.set("title", "a\n+++\nb").unwrap_err(),
+                Error::DelimiterInFrontmatter("TOML")
+            ));
+            assert_eq!(editor.to_string(), DOC);
+
+            let doc = "---toml\ntitle = 'Hello'\n---\n";
+            let mut editor = Editor::new(doc).unwrap();
+            editor.set("title", "a\n+++\nb").unwrap();
+            assert!(editor.set("title", "a\n---\nb").is_err());
+        }
+
+        #[test]
+        fn remove() {
+            let mut editor = Editor::new(DOC).unwrap();
+            assert!(editor.remove("tags"));
+            assert!(!editor.remove("absent"));
+            assert_eq!(
+                editor.to_string(),
+                DOC.replace("tags = [\"a\",   \"b\"]\n", "")
+            );
+        }
+
+        #[test]
+        fn invalid_syntax() {
+            let result = Editor::new("+++\nfoobar\n+++\n");
+            assert!(matches!(result.err().unwrap(), Error::InvalidToml(..)));
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    mod yaml {
+        use super::*;
+
+        const DOC: &str = "---\n# comment\ntitle: 'Hello'  # trailing\ntags:\n  - a\n\n  - b\n\n# extra\nextra: {x: 1}\n---\n  body\r\n---\n";
+
+        #[test]
+        fn get() {
+            let editor = Editor::new(DOC).unwrap();
+            assert_eq!(editor.format(), FrontmatterFormat::Yaml);
+            assert_eq!(editor.get::<String>("title").unwrap().unwrap(), "Hello");
+            assert_eq!(
+                editor.get::<Vec<String>>("tags").unwrap().unwrap(),
+                ["a", "b"]
+            );
+            assert!(editor.get::<String>("absent").unwrap().is_none());
+            assert!(matches!(
+                editor.get::<i64>("title").unwrap_err(),
+                Error::DeserializeYaml(..)
+            ));
+            let err = editor.get::<Vec<i64>>("tags").unwrap_err();
+            assert_eq!(err.path(), Some("tags[0]"));
+        }
+
+        #[test]
+        fn set_existing() {
+            let mut editor = Editor::new(DOC).unwrap();
+            editor.set("tags", &["c"]).unwrap();
+            assert_eq!(
+                editor.to_string(),
+                DOC.replace("tags:\n  - a\n\n  - b\n", "tags:\n- c\n")
+            );
+        }
+
+        #[test]
+        fn set_new() {
+            let mut editor = Editor::new(DOC).unwrap();
+            editor.set("draft", &true).unwrap();
+            assert_eq!(
+                editor.to_string(),
+                DOC.replace("extra: {x: 1}\n", "extra: {x: 1}\ndraft: true\n")
+            );
+        }
+
+        #[test]
+        fn set_into_empty() {
+            let mut editor = Editor::new("---\n{}\n---\nbody").unwrap();
+            editor.set("draft", &true).unwrap();
+            assert_eq!(editor.to_string(), "---\ndraft: true\n---\nbody");
+        }
+
+        #[test]
+        fn remove() {
+            let mut editor = Editor::new(DOC).unwrap();
+            assert!(editor.remove("tags"));
+            assert!(!editor.remove("absent"));
+            assert_eq!(
+                editor.to_string(),
+                DOC.replace("tags:\n  - a\n\n  - b\n", "")
+            );
+        }
+
+        #[test]
+        fn flow_mapping_not_editable() {
+            let result = Editor::new("---\n{a: 1}\n---\n");
+            assert!(matches!(result.err().unwrap(), Error::NotEditable("YAML")));
+        }
+
+        #[test]
+        fn indented_mapping_not_editable() {
+            let result = Editor::new("---\n  title: A\n  draft: true\n---\n");
+            assert!(matches!(result.err().unwrap(), Error::NotEditable("YAML")));
+        }
+
+        #[test]
+        fn invalid_syntax() {
+            let result = Editor::new("---\n:\n---\n");
+            assert!(matches!(result.err().unwrap(), Error::InvalidYaml(..)));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+
+use crate::Value;
+
+/// A boxed error returned by custom formats.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A user-defined frontmatter format.
+///
+/// Register it with [`Parser::format`](crate::Parser::format) to make it
+/// participate in splitting and parsing alongside the built-in formats. The
+/// parsed [`Value`] is deserialized into the target type, so a format only has
+/// to produce it.
+///
+/// ```
+/// use markdown_frontmatter::{BoxError, Format, Parser, Value};
+///
+/// /// `key: value` lines between `%%%` delimiters.
+/// #[derive(Debug)]
+/// struct KeyValue;
+///
+/// impl Format for KeyValue {
+///     fn name(&self) -> &'static str {
+///         "key-value"
+///     }
+///
+///     fn delimiters(&self) -> (&'static str, &'static str) {
+///         ("%%%", "%%%")
+///     }
+///
+///     fn parse(&self, matter: &str) -> Result<Value, BoxError> {
+///         matter
+///             .lines()
+///             .map(|line| {
+///                 let (key, value) = line.split_once(": ").ok_or("expected `key: value`")?;
+///                 Ok((key.into(), Value::String(value.into())))
+///             })
+///             .collect::<Result<_, BoxError>>()
+///             .map(Value::Table)
+///     }
+/// }
+///
+/// #[derive(serde::Deserialize)]
+/// struct Frontmatter {
+///     title: String,
+/// }
+///
+/// let doc = "%%%\ntitle: Hello\n%%%\nWorld";
+///
+/// let (frontmatter, body) = Parser::new()
+///     .format(&KeyValue)
+///     .parse::<Frontmatter>(doc)
+///     .unwrap();
+/// assert_eq!(frontmatter.title, "Hello");
+/// assert_eq!(body, "World");
+/// ```
+pub trait Format: fmt::Debug + Send + Sync {
+    /// Returns the name of the format used in errors, e.g. `TOML`.
+    fn name(&self) -> &'static str;
+
+    /// Returns the opening and closing delimiters of the format.
+    fn delimiters(&self) -> (&'static str, &'static str);
+
+    /// Returns `true` if a line opens the frontmatter, defaults to matching
+    /// the opening delimiter.
+    fn is_opening(&self, line: &str) -> bool {
+        line == self.delimiters().0
+    }
+
+    /// Returns `true` if a line closes the frontmatter, defaults to matching
+    /// the closing delimiter.
+    fn is_closing(&self, line: &str) -> bool {
+        line == self.delimiters().1
+    }
+
+    /// Returns `true` if the delimiter lines are a part of the frontmatter
+    /// itself, like curly brackets of JSON.
+    fn include_delimiters(&self) -> bool {
+        false
+    }
+
+    /// Parses the raw frontmatter, as returned by
+    /// [`SplitFrontmatter::as_str`](crate::SplitFrontmatter::as_str).
+    fn parse(&self, matter: &str) -> Result<Value, BoxError>;
+}
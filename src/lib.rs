@@ -1,65 +1,307 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
 
+use std::{fmt, ops::Range};
+
+#[cfg(all(feature = "cache", feature = "_serde"))]
+pub use cache::Cache;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml", feature = "ron"))]
+pub use convert::convert;
+#[cfg(all(feature = "edit", any(feature = "toml", feature = "yaml")))]
+pub use edit::Editor;
+#[cfg(feature = "_serde")]
+pub use format::{BoxError, Format};
+pub use parser::Parser;
+#[cfg(feature = "_serde")]
+pub use value::{Table, Value};
+
+#[cfg(all(feature = "cache", feature = "_serde"))]
+mod cache;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml", feature = "ron"))]
+mod convert;
+#[cfg(feature = "_serde")]
+pub mod corpus;
+#[cfg(feature = "miette")]
+mod diagnostic;
+#[cfg(all(feature = "edit", any(feature = "toml", feature = "yaml")))]
+mod edit;
+#[cfg(feature = "_serde")]
+mod format;
+#[cfg(feature = "mmd")]
+mod mmd;
+mod parser;
+#[cfg(feature = "_serde")]
+mod value;
+#[cfg(all(feature = "watch", feature = "_serde"))]
+pub mod watch;
+
 /// The format of the frontmatter.
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum FrontmatterFormat {
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum FrontmatterFormat {
     /// JSON frontmatter, denoted by `{...}`.
     Json,
+    /// JSON5 frontmatter, denoted by `{...}` like JSON, detected instead of
+    /// JSON by a parser with [`Parser::json5`] enabled.
+    Json5,
     /// TOML frontmatter, denoted by `+++...+++`.
     Toml,
     /// YAML frontmatter, denoted by `---...---`.
     Yaml,
+    /// RON frontmatter, denoted by `(...)`, detected only with the `ron`
+    /// feature.
+    Ron,
+    /// MultiMarkdown metadata, `Key: value` lines ended by a blank line.
+    /// Detection is heuristic, so it's detected only by a parser with
+    /// [`Parser::mmd`] enabled.
+    Mmd,
+    #[cfg(feature = "_serde")]
+    /// User-defined format registered with [`Parser::format`], custom formats
+    /// are compared by their names.
+    Custom(&'static dyn Format),
 }
 
 impl From<FrontmatterFormat> for &'static str {
     fn from(format: FrontmatterFormat) -> Self {
         match format {
             FrontmatterFormat::Json => "JSON",
+            FrontmatterFormat::Json5 => "JSON5",
             FrontmatterFormat::Toml => "TOML",
             FrontmatterFormat::Yaml => "YAML",
+            FrontmatterFormat::Ron => "RON",
+            FrontmatterFormat::Mmd => "MMD",
+            #[cfg(feature = "_serde")]
+            FrontmatterFormat::Custom(format) => format.name(),
+        }
+    }
+}
+
+impl PartialEq for FrontmatterFormat {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_custom() == other.is_custom() && <&str>::from(*self) == <&str>::from(*other)
+    }
+}
+
+impl Eq for FrontmatterFormat {}
+
+impl std::hash::Hash for FrontmatterFormat {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.is_custom().hash(state);
+        <&str>::from(*self).hash(state);
+    }
+}
+
+/// A location in the original document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Byte offset of the start of the span.
+    pub start: usize,
+    /// Byte offset of the end of the span, exclusive.
+    pub end: usize,
+    /// One-based line of the start of the span.
+    pub line: usize,
+    /// One-based column of the start of the span, in characters.
+    pub column: usize,
+}
+
+impl Span {
+    /// Creates a span of a byte range within a document.
+    fn new(content: &str, range: Range<usize>) -> Self {
+        let mut line = 1;
+        let mut line_start = 0;
+        for span in LineSpan::new(content) {
+            if span.next_start > range.start || span.next_start == content.len() {
+                break;
+            }
+            line += 1;
+            line_start = span.next_start;
         }
+        Self {
+            start: range.start,
+            end: range.end,
+            line,
+            column: content[line_start..range.start].chars().count() + 1,
+        }
+    }
+
+    /// Returns the byte range of the span.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
     }
 }
 
 /// The crate's error type
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    /// Frontmatter format is disabled.
+    /// Frontmatter format is disabled, the span points to the opening
+    /// delimiter of a parsed document, there's none for serialization.
     #[error("disabled format {0}, enable corresponding cargo feature")]
-    DisabledFormat(&'static str),
-    /// Closing delimiter is absent.
-    #[error("absent closing {0} delimiter")]
-    AbsentClosingDelimiter(&'static str),
+    DisabledFormat(&'static str, Option<Span>),
+    /// Closing delimiter is absent, the span points to the opening one,
+    /// followed by the expected closing line, which is empty for MMD.
+    #[error("absent closing {0} delimiter for the opening one at {1}")]
+    AbsentClosingDelimiter(&'static str, Span, String),
+    /// Document has no frontmatter.
+    #[error("absent frontmatter")]
+    AbsentFrontmatter,
+    #[cfg(all(feature = "edit", any(feature = "toml", feature = "yaml")))]
+    /// Frontmatter format doesn't support editing.
+    #[error("editing {0} frontmatter isn't supported")]
+    NotEditable(&'static str),
+    /// Frontmatter format doesn't support serialization.
+    #[error("serializing {0} frontmatter isn't supported")]
+    NotSerializable(&'static str),
 
     #[cfg(feature = "json")]
     /// Invalid JSON syntax.
-    #[error("invalid JSON syntax")]
-    InvalidJson(#[source] serde_json::Error),
+    #[error("invalid JSON syntax at {1}")]
+    InvalidJson(#[source] serde_json::Error, Span),
+    #[cfg(feature = "json5")]
+    /// Invalid JSON5 syntax.
+    #[error("invalid JSON5 syntax at {1}")]
+    InvalidJson5(#[source] json5::Error, Span),
     #[cfg(feature = "toml")]
     /// Invalid TOML syntax.
-    #[error("invalid TOML syntax")]
-    InvalidToml(#[source] toml::de::Error),
+    #[error("invalid TOML syntax at {1}")]
+    InvalidToml(#[source] toml::de::Error, Span),
     #[cfg(feature = "yaml")]
     /// Invalid YAML syntax.
-    #[error("invalid YAML syntax")]
-    InvalidYaml(#[source] serde_yaml::Error),
+    #[error("invalid YAML syntax at {1}")]
+    InvalidYaml(#[source] serde_yaml::Error, Span),
+    #[cfg(feature = "ron")]
+    /// Invalid RON syntax.
+    #[error("invalid RON syntax at {1}")]
+    InvalidRon(#[source] Box<ron::error::SpannedError>, Span),
+    #[cfg(feature = "_serde")]
+    /// Invalid syntax of a custom format or MultiMarkdown metadata.
+    #[error("invalid {0} syntax at {2}")]
+    Invalid(&'static str, #[source] BoxError, Span),
 
     #[cfg(feature = "json")]
-    /// Couldn't deserialize JSON into the target type.
-    #[error("couldn't deserialize JSON")]
-    DeserializeJson(#[source] serde_json::Error),
+    /// Couldn't deserialize JSON into the target type, the path points to
+    /// the failed field, e.g. `authors[2].email`.
+    #[error("couldn't deserialize JSON field `{2}` at {1}")]
+    DeserializeJson(#[source] serde_json::Error, Span, String),
+    #[cfg(feature = "json5")]
+    /// Couldn't deserialize JSON5 into the target type, the path points to
+    /// the failed field.
+    #[error("couldn't deserialize JSON5 field `{2}` at {1}")]
+    DeserializeJson5(#[source] json5::Error, Span, String),
     #[cfg(feature = "toml")]
-    /// Couldn't deserialize TOML into the target type.
-    #[error("couldn't deserialize TOML")]
-    DeserializeToml(#[source] toml::de::Error),
+    /// Couldn't deserialize TOML into the target type, the path points to
+    /// the failed field, e.g. `authors[2].email`.
+    #[error("couldn't deserialize TOML field `{2}` at {1}")]
+    DeserializeToml(#[source] Box<toml::de::Error>, Span, String),
     #[cfg(feature = "yaml")]
-    /// Couldn't deserialize YAML into the target type.
-    #[error("couldn't deserialize YAML")]
-    DeserializeYaml(#[source] serde_yaml::Error),
+    /// Couldn't deserialize YAML into the target type, the path points to
+    /// the failed field, e.g. `authors[2].email`.
+    #[error("couldn't deserialize YAML field `{2}` at {1}")]
+    DeserializeYaml(#[source] serde_yaml::Error, Span, String),
+    #[cfg(feature = "ron")]
+    /// Couldn't deserialize RON into the target type, the path points to the
+    /// failed field.
+    #[error("couldn't deserialize RON field `{2}` at {1}")]
+    DeserializeRon(#[source] Box<ron::error::SpannedError>, Span, String),
+    #[cfg(feature = "_serde")]
+    /// Couldn't deserialize a custom format or MultiMarkdown metadata into the
+    /// target type.
+    #[error("couldn't deserialize {0} field `{3}` at {2}")]
+    Deserialize(
+        &'static str,
+        #[source] serde::de::value::Error,
+        Span,
+        String,
+    ),
+
+    #[cfg(feature = "json")]
+    /// Couldn't serialize frontmatter into JSON.
+    #[error("couldn't serialize JSON")]
+    SerializeJson(#[source] serde_json::Error),
+    #[cfg(feature = "toml")]
+    /// Couldn't serialize frontmatter into TOML.
+    #[error("couldn't serialize TOML")]
+    SerializeToml(#[source] toml::ser::Error),
+    #[cfg(feature = "yaml")]
+    /// Couldn't serialize frontmatter into YAML.
+    #[error("couldn't serialize YAML")]
+    SerializeYaml(#[source] serde_yaml::Error),
+    #[cfg(feature = "ron")]
+    /// Couldn't serialize frontmatter into RON.
+    #[error("couldn't serialize RON")]
+    SerializeRon(#[source] ron::Error),
+    /// A value can't be represented in the target format.
+    #[error("{0} at `{1}` can't be represented in {2}")]
+    Unrepresentable(&'static str, String, &'static str),
+    /// Serialized frontmatter contains a line equal to its closing delimiter,
+    /// so it can't be parsed back.
+    #[error("serialized {0} frontmatter contains its closing delimiter")]
+    DelimiterInFrontmatter(&'static str),
+
+    /// I/O error.
+    #[error("I/O error")]
+    Io(#[source] std::io::Error),
+
+    #[cfg(feature = "watch")]
+    /// Error of watching a directory.
+    #[error("watch error")]
+    Watch(#[source] notify::Error),
+}
+
+impl Error {
+    /// Returns the location of the error in the original document, if the
+    /// error is caused by the document content.
+    ///
+    /// Syntax errors point to the exact position reported by the format
+    /// parser, while errors without a known position point to the whole
+    /// frontmatter.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::DisabledFormat(_, span) => *span,
+            Self::AbsentClosingDelimiter(_, span, _) => Some(*span),
+            #[cfg(feature = "json")]
+            Self::InvalidJson(_, span) | Self::DeserializeJson(_, span, _) => Some(*span),
+            #[cfg(feature = "json5")]
+            Self::InvalidJson5(_, span) | Self::DeserializeJson5(_, span, _) => Some(*span),
+            #[cfg(feature = "toml")]
+            Self::InvalidToml(_, span) | Self::DeserializeToml(_, span, _) => Some(*span),
+            #[cfg(feature = "yaml")]
+            Self::InvalidYaml(_, span) | Self::DeserializeYaml(_, span, _) => Some(*span),
+            #[cfg(feature = "ron")]
+            Self::InvalidRon(_, span) | Self::DeserializeRon(_, span, _) => Some(*span),
+            #[cfg(feature = "_serde")]
+            Self::Invalid(_, _, span) | Self::Deserialize(_, _, span, _) => Some(*span),
+            _ => None,
+        }
+    }
+
+    /// Returns the path to the field that failed to deserialize, e.g.
+    /// `authors[2].email`, or `.` for the frontmatter itself.
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            #[cfg(feature = "json")]
+            Self::DeserializeJson(_, _, path) => Some(path),
+            #[cfg(feature = "json5")]
+            Self::DeserializeJson5(_, _, path) => Some(path),
+            #[cfg(feature = "toml")]
+            Self::DeserializeToml(_, _, path) => Some(path),
+            #[cfg(feature = "yaml")]
+            Self::DeserializeYaml(_, _, path) => Some(path),
+            #[cfg(feature = "ron")]
+            Self::DeserializeRon(_, _, path) => Some(path),
+            #[cfg(feature = "_serde")]
+            Self::Deserialize(_, _, _, path) => Some(path),
+            _ => None,
+        }
+    }
 }
 
-#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+#[cfg(feature = "_serde")]
 /// Parses frontmatter from a markdown string, deserializing it into a given
 /// type and returning the parsed frontmatter and the body of the document.
 ///
@@ -89,120 +331,675 @@ pub enum Error {
 /// assert_eq!(body, "World\n");
 /// ```
 pub fn parse<T: serde::de::DeserializeOwned>(content: &str) -> Result<(T, &str), Error> {
-    let (maybe_frontmatter, body) = split(content)?;
-    let SplitFrontmatter(format, matter_str) = maybe_frontmatter.unwrap_or_default();
-    let frontmatter = format.parse(matter_str)?;
-    Ok((frontmatter, body))
+    parse_borrowed(content)
 }
 
-#[derive(Debug, Clone, Copy)]
-struct SplitFrontmatter<'a>(FrontmatterFormat, &'a str);
+#[cfg(feature = "_serde")]
+/// Parses frontmatter like [`parse`], but allows the frontmatter to borrow
+/// from the document.
+///
+/// Whether a string can be borrowed depends on the format: strings with
+/// escape sequences, e.g. `"a\"b"` in JSON, have to be unescaped into a new
+/// string, so deserializing them into `&str` fails. Use `Cow<'a, str>` with
+/// `#[serde(borrow)]` to borrow where possible and allocate otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use markdown_frontmatter::parse_borrowed;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct MyFrontmatter<'a> {
+///     title: &'a str,
+/// }
+///
+/// let doc = "{\n  \"title\": \"Hello\"\n}\nWorld\n";
+///
+/// let (frontmatter, body) = parse_borrowed::<MyFrontmatter<'_>>(doc).unwrap();
+/// assert_eq!(frontmatter.title, "Hello");
+/// assert_eq!(body, "World\n");
+/// ```
+pub fn parse_borrowed<'de, T: serde::Deserialize<'de>>(
+    content: &'de str,
+) -> Result<(T, &'de str), Error> {
+    Parser::new().parse_borrowed(content)
+}
+
+/// Reader of the body returned by [`parse_reader`], the lines read to tell if
+/// frontmatter opens followed by the rest of the reader.
+#[cfg(feature = "_serde")]
+pub type BodyReader<R> = std::io::Chain<std::io::Cursor<Vec<u8>>, R>;
+
+/// Async counterpart of [`BodyReader`] returned by [`parse_async_reader`].
+#[cfg(all(feature = "async", feature = "_serde"))]
+pub type AsyncBodyReader<R> = futures_util::io::Chain<futures_util::io::Cursor<Vec<u8>>, R>;
+
+#[cfg(feature = "_serde")]
+/// Parses frontmatter like [`parse`], but from a reader, reading only up to
+/// the end of the frontmatter.
+///
+/// Returns the frontmatter along with a reader of the body, so it can be read
+/// on or just dropped if only the frontmatter is needed. The opening lines are
+/// peeked at in the buffer of the reader, so a document without frontmatter
+/// stays unread, except for leading whitespace. Opening lines that don't fit
+/// the buffer are read, and the body reader returns them first.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{BufReader, Read};
+///
+/// use markdown_frontmatter::parse_reader;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct MyFrontmatter {
+///     title: String,
+/// }
+///
+/// let doc = "---\ntitle: Hello\n---\nWorld\n";
+/// let reader = BufReader::new(doc.as_bytes());
+///
+/// let (frontmatter, mut body_reader) = parse_reader::<MyFrontmatter, _>(reader).unwrap();
+/// assert_eq!(frontmatter.title, "Hello");
+///
+/// let mut body = String::new();
+/// body_reader.read_to_string(&mut body).unwrap();
+/// assert_eq!(body, "World\n");
+/// ```
+pub fn parse_reader<T: serde::de::DeserializeOwned, R: std::io::BufRead>(
+    reader: R,
+) -> Result<(T, BodyReader<R>), Error> {
+    Parser::new().parse_reader(reader)
+}
+
+#[cfg(all(feature = "async", feature = "_serde"))]
+/// Parses frontmatter like [`parse_reader`], but from an async reader.
+///
+/// # Examples
+///
+/// ```
+/// use futures_util::{AsyncReadExt, io::Cursor};
+/// use markdown_frontmatter::parse_async_reader;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct MyFrontmatter {
+///     title: String,
+/// }
+///
+/// # futures_executor::block_on(async {
+/// let reader = Cursor::new("---\ntitle: Hello\n---\nWorld\n");
+///
+/// let (frontmatter, mut body_reader) = parse_async_reader::<MyFrontmatter, _>(reader)
+///     .await
+///     .unwrap();
+/// assert_eq!(frontmatter.title, "Hello");
+///
+/// let mut body = String::new();
+/// body_reader.read_to_string(&mut body).await.unwrap();
+/// assert_eq!(body, "World\n");
+/// # });
+/// ```
+pub async fn parse_async_reader<
+    T: serde::de::DeserializeOwned,
+    R: futures_util::AsyncBufRead + Unpin,
+>(
+    reader: R,
+) -> Result<(T, AsyncBodyReader<R>), Error> {
+    Parser::new().parse_async_reader(reader).await
+}
+
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml", feature = "ron"))]
+/// Serializes frontmatter in a given format and prepends it to the body,
+/// returning the resulting document.
+///
+/// The document is framed by the format delimiters, so [`parse`] round-trips
+/// it, returning the same frontmatter and exactly the same body.
+///
+/// # Arguments
+///
+/// * `frontmatter` - The frontmatter to serialize.
+/// * `format` - The format of the frontmatter.
+/// * `body` - The body of the document.
+///
+/// # Examples
+///
+/// ```
+/// use markdown_frontmatter::{FrontmatterFormat, to_string};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct MyFrontmatter {
+///     title: String,
+/// }
+///
+/// let frontmatter = MyFrontmatter {
+///     title: "Hello".into(),
+/// };
+/// let doc = to_string(&frontmatter, FrontmatterFormat::Toml, "World\n").unwrap();
+/// assert_eq!(doc, "+++\ntitle = \"Hello\"\n+++\nWorld\n");
+/// ```
+pub fn to_string<T: serde::Serialize + ?Sized>(
+    frontmatter: &T,
+    format: FrontmatterFormat,
+    body: &str,
+) -> Result<String, Error> {
+    let matter = format.serialize(frontmatter)?;
+    let mut doc = String::with_capacity(matter.len() + body.len() + 8);
+    if format.include_delimiters() {
+        doc.push_str(&matter);
+    } else {
+        let (opening, closing) = format.delimiter();
+        doc.push_str(opening);
+        doc.push('\n');
+        doc.push_str(&matter);
+        doc.push_str(closing);
+        doc.push('\n');
+    }
+    doc.push_str(body);
+    Ok(doc)
+}
+
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml", feature = "ron"))]
+/// Serializes a document like [`to_string`] and writes it into a writer.
+pub fn write<W: std::io::Write, T: serde::Serialize + ?Sized>(
+    mut writer: W,
+    frontmatter: &T,
+    format: FrontmatterFormat,
+    body: &str,
+) -> Result<(), Error> {
+    let doc = to_string(frontmatter, format, body)?;
+    writer.write_all(doc.as_bytes()).map_err(Error::Io)
+}
+
+/// Raw frontmatter extracted from a document by [`split`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitFrontmatter<'a> {
+    format: FrontmatterFormat,
+    matter: &'a str,
+    start: usize,
+    end: usize,
+    /// Span of the opening delimiter, `None` for substituted frontmatter.
+    opening: Option<Span>,
+}
 
-#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+impl<'a> SplitFrontmatter<'a> {
+    /// Returns the detected format of the frontmatter.
+    pub fn format(&self) -> FrontmatterFormat {
+        self.format
+    }
+
+    /// Returns the raw frontmatter string.
+    ///
+    /// TOML and YAML delimiters are stripped, while JSON curly brackets are
+    /// kept as they're a part of the JSON object.
+    pub fn as_str(&self) -> &'a str {
+        self.matter
+    }
+
+    /// Returns the byte range of the raw frontmatter within the original
+    /// document.
+    pub fn span(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+#[cfg(feature = "_serde")]
 impl Default for SplitFrontmatter<'_> {
     fn default() -> Self {
         #[cfg(feature = "json")]
         {
-            Self(FrontmatterFormat::Json, "{}")
+            Self::empty(FrontmatterFormat::Json)
         }
         #[cfg(all(not(feature = "json"), feature = "toml"))]
         {
-            Self(FrontmatterFormat::Toml, "")
+            Self::empty(FrontmatterFormat::Toml)
         }
         #[cfg(all(not(any(feature = "json", feature = "toml")), feature = "yaml"))]
         {
-            Self(FrontmatterFormat::Yaml, "{}")
+            Self::empty(FrontmatterFormat::Yaml)
+        }
+        #[cfg(all(
+            not(any(feature = "json", feature = "toml", feature = "yaml")),
+            feature = "ron"
+        ))]
+        {
+            Self::empty(FrontmatterFormat::Ron)
+        }
+        #[cfg(all(
+            not(any(feature = "json", feature = "toml", feature = "yaml", feature = "ron")),
+            feature = "mmd"
+        ))]
+        {
+            Self::empty(FrontmatterFormat::Mmd)
         }
     }
 }
 
-/// Splits a document into frontmatter and body, returning the raw frontmatter
-/// string and the body of the document.
-fn split(content: &str) -> Result<(Option<SplitFrontmatter<'_>>, &str), Error> {
-    let content = content.trim_start();
-    let mut lines = LineSpan::new(content);
-
-    let Some(span) = lines.next() else {
-        // Empty document
-        return Ok((None, content));
-    };
-
-    let Some(format) = FrontmatterFormat::detect(span.line) else {
-        // No frontmatter
-        return Ok((None, content));
-    };
-
-    let matter_start = match format {
-        FrontmatterFormat::Json => span.start, // include opening curly bracket,
-        FrontmatterFormat::Toml | FrontmatterFormat::Yaml => span.next_start,
-    };
-
-    let closing_delimiter = format.delimiter().1;
-    for span in lines {
-        if span.line != closing_delimiter {
-            continue;
-        }
-        let (matter, body) = match format {
-            FrontmatterFormat::Json => (
-                &content[matter_start..span.next_start], // include closing curly bracket
-                &content[span.next_start..],
-            ),
-            FrontmatterFormat::Toml | FrontmatterFormat::Yaml => (
-                &content[matter_start..span.start], // exclude closing delimiter
-                &content[span.next_start..],
-            ),
+#[cfg(feature = "_serde")]
+impl<'a> SplitFrontmatter<'a> {
+    /// Frontmatter substituted for documents without one, it doesn't point
+    /// into the document, so its span is empty.
+    fn empty(format: FrontmatterFormat) -> Self {
+        let matter = match format {
+            FrontmatterFormat::Json | FrontmatterFormat::Json5 | FrontmatterFormat::Yaml => "{}",
+            FrontmatterFormat::Ron => "()",
+            FrontmatterFormat::Toml | FrontmatterFormat::Mmd | FrontmatterFormat::Custom(_) => "",
+        };
+        Self {
+            format,
+            matter,
+            start: 0,
+            end: 0,
+            opening: None,
+        }
+    }
+
+    /// Deserializes the frontmatter, `content` is the original document used
+    /// to locate errors.
+    fn parse<T: serde::Deserialize<'a>>(&self, content: &str) -> Result<T, Error> {
+        let matter_str = self.matter;
+        match self.format {
+            #[cfg(feature = "json")]
+            FrontmatterFormat::Json => {
+                let locate = |e: &serde_json::Error| {
+                    let offset = line_column_offset(matter_str, e.line(), e.column());
+                    self.locate(content, offset.map(|offset| char_range(matter_str, offset)))
+                };
+                let error = |e: serde_json::Error, path| {
+                    let span = locate(&e);
+                    match e.classify() {
+                        serde_json::error::Category::Data => Error::DeserializeJson(e, span, path),
+                        _ => Error::InvalidJson(e, span),
+                    }
+                };
+                let mut json = serde_json::Deserializer::from_str(matter_str);
+                let value = deserialize(&mut json, "").map_err(|(e, path)| error(e, path))?;
+                json.end().map_err(|e| error(e, ".".into()))?;
+                Ok(value)
+            }
+            #[cfg(not(feature = "json"))]
+            FrontmatterFormat::Json => Err(Error::DisabledFormat(
+                FrontmatterFormat::Json.into(),
+                self.opening,
+            )),
+
+            #[cfg(feature = "json5")]
+            FrontmatterFormat::Json5 => {
+                let locate = |e: &json5::Error| {
+                    let json5::Error::Message { location, .. } = e;
+                    let offset = location
+                        .as_ref()
+                        .and_then(|l| char_column_offset(matter_str, l.line, l.column));
+                    self.locate(content, offset.map(|offset| char_range(matter_str, offset)))
+                };
+                // The whole frontmatter is parsed upfront, so the following
+                // errors are deserialization ones
+                let mut json5 = json5::Deserializer::from_str(matter_str).map_err(|e| {
+                    let span = locate(&e);
+                    Error::InvalidJson5(e, span)
+                })?;
+                deserialize(&mut json5, "").map_err(|(e, path)| {
+                    let span = locate(&e);
+                    Error::DeserializeJson5(e, span, path)
+                })
+            }
+            #[cfg(not(feature = "json5"))]
+            FrontmatterFormat::Json5 => Err(Error::DisabledFormat(
+                FrontmatterFormat::Json5.into(),
+                self.opening,
+            )),
+
+            #[cfg(feature = "toml")]
+            FrontmatterFormat::Toml => {
+                let locate = |e: &toml::de::Error| self.locate(content, e.span());
+                // Unlike `toml::Value`, the deserializer keeps datetimes
+                let toml = toml::de::Deserializer::parse(matter_str).map_err(|e| {
+                    let span = locate(&e);
+                    Error::InvalidToml(e, span)
+                })?;
+                deserialize(toml, "").map_err(|(e, path)| {
+                    let span = locate(&e);
+                    Error::DeserializeToml(Box::new(e), span, path)
+                })
+            }
+            #[cfg(not(feature = "toml"))]
+            FrontmatterFormat::Toml => Err(Error::DisabledFormat(
+                FrontmatterFormat::Toml.into(),
+                self.opening,
+            )),
+
+            #[cfg(feature = "yaml")]
+            FrontmatterFormat::Yaml => {
+                let locate = |e: &serde_yaml::Error| {
+                    let range = e.location().map(|l| char_range(matter_str, l.index()));
+                    self.locate(content, range)
+                };
+                let yaml = serde_yaml::Deserializer::from_str(matter_str);
+                deserialize(yaml, "").map_err(|(e, path)| {
+                    // The error doesn't tell syntax errors apart, so look for
+                    // one only on failure to keep the happy path single-pass
+                    if let Err(e) = serde_yaml::from_str::<serde::de::IgnoredAny>(matter_str) {
+                        let span = locate(&e);
+                        return Error::InvalidYaml(e, span);
+                    }
+                    let span = locate(&e);
+                    Error::DeserializeYaml(e, span, path)
+                })
+            }
+            #[cfg(not(feature = "yaml"))]
+            FrontmatterFormat::Yaml => Err(Error::DisabledFormat(
+                FrontmatterFormat::Yaml.into(),
+                self.opening,
+            )),
+
+            #[cfg(feature = "ron")]
+            FrontmatterFormat::Ron => {
+                let locate = |e: &ron::error::SpannedError| {
+                    let position = e.span.start;
+                    let offset = char_column_offset(matter_str, position.line, position.col);
+                    self.locate(content, offset.map(|offset| char_range(matter_str, offset)))
+                };
+                let mut ron = ron::Deserializer::from_str_with_options(matter_str, &ron_options())
+                    .map_err(|e| {
+                        let span = locate(&e);
+                        Error::InvalidRon(Box::new(e), span)
+                    })?;
+                let value = deserialize(&mut ron, "")
+                    .and_then(|value| ron.end().map(|()| value).map_err(|e| (e, ".".into())))
+                    .map_err(|(e, path)| {
+                        let e = ron.span_error(e);
+                        // Look for a syntax error only on failure, like YAML
+                        if let Err(e) = ron_options().from_str::<serde::de::IgnoredAny>(matter_str)
+                        {
+                            let span = locate(&e);
+                            return Error::InvalidRon(Box::new(e), span);
+                        }
+                        let span = locate(&e);
+                        Error::DeserializeRon(Box::new(e), span, path)
+                    })?;
+                Ok(value)
+            }
+            #[cfg(not(feature = "ron"))]
+            FrontmatterFormat::Ron => Err(Error::DisabledFormat(
+                FrontmatterFormat::Ron.into(),
+                self.opening,
+            )),
+
+            #[cfg(feature = "mmd")]
+            FrontmatterFormat::Mmd => {
+                let value = mmd::parse(matter_str).map_err(|(message, range)| {
+                    let span = self.locate(content, Some(range));
+                    Error::Invalid(FrontmatterFormat::Mmd.into(), message.into(), span)
+                })?;
+                deserialize(value, "").map_err(|(e, path)| {
+                    let span = self.locate(content, None);
+                    Error::Deserialize(FrontmatterFormat::Mmd.into(), e, span, path)
+                })
+            }
+            #[cfg(not(feature = "mmd"))]
+            FrontmatterFormat::Mmd => Err(Error::DisabledFormat(
+                FrontmatterFormat::Mmd.into(),
+                self.opening,
+            )),
+
+            FrontmatterFormat::Custom(format) => {
+                let value = format
+                    .parse(matter_str)
+                    .map_err(|e| Error::Invalid(format.name(), e, self.locate(content, None)))?;
+                deserialize(value, "").map_err(|(e, path)| {
+                    Error::Deserialize(format.name(), e, self.locate(content, None), path)
+                })
+            }
+        }
+    }
+}
+
+impl SplitFrontmatter<'_> {
+    /// Maps a byte range within the raw frontmatter to a span in the original
+    /// document, falling back to the whole frontmatter if the range is unknown
+    /// or the frontmatter isn't a part of the document.
+    #[cfg_attr(not(feature = "_serde"), expect(dead_code))]
+    fn locate(&self, content: &str, range: Option<Range<usize>>) -> Span {
+        let points_into_content = self.end - self.start == self.matter.len();
+        let range = match range {
+            Some(range) if points_into_content && range.end <= self.matter.len() => {
+                self.start + range.start..self.start + range.end
+            }
+            _ => self.span(),
         };
-        return Ok((Some(SplitFrontmatter(format, matter)), body));
+        Span::new(content, range)
+    }
+}
+
+/// Deserializes a value, tracking the path to the failed field, the path is
+/// appended to the `prefix`.
+#[cfg(feature = "_serde")]
+fn deserialize<'de, D, T>(deserializer: D, prefix: &str) -> Result<T, (D::Error, String)>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    serde_path_to_error::deserialize(deserializer).map_err(|e| {
+        let path = e.path().to_string();
+        let path = match path.as_str() {
+            _ if prefix.is_empty() => path,
+            "." => prefix.into(),
+            _ if path.starts_with('[') => format!("{prefix}{path}"),
+            _ => format!("{prefix}.{path}"),
+        };
+        (e.into_inner(), path)
+    })
+}
+
+/// Converts a one-based line and column into a byte offset.
+#[cfg(feature = "json")]
+fn line_column_offset(s: &str, line: usize, column: usize) -> Option<usize> {
+    let span = LineSpan::new(s).nth(line.checked_sub(1)?)?;
+    let offset = span.start + column.saturating_sub(1);
+    Some(offset.min(span.next_start))
+}
+
+/// Converts a one-based line and column in characters into a byte offset.
+#[cfg(any(feature = "json5", feature = "ron"))]
+fn char_column_offset(s: &str, line: usize, column: usize) -> Option<usize> {
+    let span = LineSpan::new(s).nth(line.checked_sub(1)?)?;
+    let column = span.line.char_indices().nth(column.saturating_sub(1));
+    Some(span.start + column.map_or(span.line.len(), |(i, _)| i))
+}
+
+/// RON options with optional fields written without `Some(..)`, as in other
+/// formats.
+#[cfg(feature = "ron")]
+fn ron_options() -> ron::Options {
+    ron::Options::default().with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+}
+
+/// Returns the byte range of a character at an offset, or an empty range at
+/// the end of the string.
+#[cfg(any(feature = "json", feature = "yaml", feature = "ron"))]
+fn char_range(s: &str, offset: usize) -> Range<usize> {
+    let mut start = offset.min(s.len());
+    while !s.is_char_boundary(start) {
+        start -= 1;
     }
-    Err(Error::AbsentClosingDelimiter(format.into()))
+    let len = s[start..].chars().next().map_or(0, char::len_utf8);
+    start..start + len
+}
+
+/// Splits a document into frontmatter and body, returning the raw frontmatter
+/// and the body of the document.
+///
+/// Leading whitespace is skipped. If the document doesn't start with a known
+/// opening delimiter, the frontmatter is `None` and the whole (trimmed)
+/// document is returned as the body. Unlike [`parse`], the frontmatter isn't
+/// deserialized, so formats disabled by cargo features are still detected.
+///
+/// # Examples
+///
+/// ```
+/// use markdown_frontmatter::{FrontmatterFormat, split};
+///
+/// let doc = "+++\ntitle = \"Hello\"\n+++\nWorld\n";
+///
+/// let (frontmatter, body) = split(doc).unwrap();
+/// let frontmatter = frontmatter.unwrap();
+/// assert_eq!(frontmatter.format(), FrontmatterFormat::Toml);
+/// assert_eq!(frontmatter.as_str(), "title = \"Hello\"\n");
+/// assert_eq!(&doc[frontmatter.span()], frontmatter.as_str());
+/// assert_eq!(body, "World\n");
+/// ```
+pub fn split(content: &str) -> Result<(Option<SplitFrontmatter<'_>>, &str), Error> {
+    Parser::new().split(content)
 }
 
 impl FrontmatterFormat {
-    const VARIANTS: [Self; 3] = [Self::Json, Self::Toml, Self::Yaml];
+    /// Built-in formats in the order of detection, JSON5 shares the JSON
+    /// delimiter, so it's never detected by [`FrontmatterFormat::detect`].
+    const VARIANTS: [Self; 5] = [Self::Json, Self::Toml, Self::Yaml, Self::Ron, Self::Json5];
 
     /// Detects the frontmatter format from the first line of a document.
-    fn detect(first_line: &str) -> Option<Self> {
+    ///
+    /// ```
+    /// use markdown_frontmatter::FrontmatterFormat;
+    ///
+    /// assert_eq!(FrontmatterFormat::detect("---"), Some(FrontmatterFormat::Yaml));
+    /// assert_eq!(FrontmatterFormat::detect("# Title"), None);
+    /// ```
+    pub fn detect(first_line: &str) -> Option<Self> {
         Self::VARIANTS
             .into_iter()
+            // A line of `(` is common in plain markdown, so RON is detected
+            // only if it's enabled
+            .filter(|&variant| cfg!(feature = "ron") || variant != Self::Ron)
             .find(|&variant| first_line == variant.delimiter().0)
     }
 
-    #[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
-    fn parse<T: serde::de::DeserializeOwned>(&self, matter_str: &str) -> Result<T, Error> {
+    fn is_custom(&self) -> bool {
         match self {
+            #[cfg(feature = "_serde")]
+            Self::Custom(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the line closes frontmatter of this format.
+    fn is_closing(&self, line: &str) -> bool {
+        match self {
+            #[cfg(feature = "_serde")]
+            Self::Custom(format) => format.is_closing(line),
+            Self::Mmd => line.trim().is_empty(),
+            _ => line == self.delimiter().1,
+        }
+    }
+
+    /// Returns `true` if the delimiters are a part of the raw frontmatter.
+    fn include_delimiters(&self) -> bool {
+        match self {
+            // The opening line of MMD is a metadata line
+            Self::Json | Self::Json5 | Self::Ron | Self::Mmd => true,
+            Self::Toml | Self::Yaml => false,
+            #[cfg(feature = "_serde")]
+            Self::Custom(format) => format.include_delimiters(),
+        }
+    }
+
+    /// Serializes a value into a raw frontmatter string, as it would be
+    /// returned by [`SplitFrontmatter::as_str`].
+    #[cfg(any(feature = "json", feature = "toml", feature = "yaml", feature = "ron"))]
+    fn serialize<T: serde::Serialize + ?Sized>(&self, value: &T) -> Result<String, Error> {
+        let mut matter = match self {
             #[cfg(feature = "json")]
             Self::Json => {
-                let json: serde_json::Value =
-                    serde_json::from_str(matter_str).map_err(Error::InvalidJson)?;
-                serde_json::from_value(json).map_err(Error::DeserializeJson)
+                let json = serde_json::to_string_pretty(value).map_err(Error::SerializeJson)?;
+                if !json.starts_with('{') {
+                    return Err(Error::SerializeJson(serde::ser::Error::custom(
+                        "frontmatter must be a JSON object",
+                    )));
+                }
+                if json == "{}" {
+                    // The opening curly bracket has to be on its own line
+                    "{\n}".into()
+                } else {
+                    json
+                }
             }
             #[cfg(not(feature = "json"))]
-            Self::Json => Err(Error::DisabledFormat(Self::Json.into())),
+            Self::Json => return Err(Error::DisabledFormat(Self::Json.into(), None)),
+
+            // JSON is valid JSON5
+            #[cfg(feature = "json5")]
+            Self::Json5 => Self::Json.serialize(value)?,
+            #[cfg(not(feature = "json5"))]
+            Self::Json5 => return Err(Error::DisabledFormat(Self::Json5.into(), None)),
 
             #[cfg(feature = "toml")]
-            Self::Toml => {
-                let toml: toml::Value = toml::from_str(matter_str).map_err(Error::InvalidToml)?;
-                toml.try_into().map_err(Error::DeserializeToml)
-            }
+            Self::Toml => value::with_toml_datetimes(|| toml::to_string(value))
+                .map_err(Error::SerializeToml)?,
             #[cfg(not(feature = "toml"))]
-            Self::Toml => Err(Error::DisabledFormat(Self::Toml.into())),
+            Self::Toml => return Err(Error::DisabledFormat(Self::Toml.into(), None)),
 
             #[cfg(feature = "yaml")]
-            Self::Yaml => {
-                let yaml: serde_yaml::Value =
-                    serde_yaml::from_str(matter_str).map_err(Error::InvalidYaml)?;
-                serde_yaml::from_value(yaml).map_err(Error::DeserializeYaml)
-            }
+            Self::Yaml => serde_yaml::to_string(value).map_err(Error::SerializeYaml)?,
             #[cfg(not(feature = "yaml"))]
-            Self::Yaml => Err(Error::DisabledFormat(Self::Yaml.into())),
+            Self::Yaml => return Err(Error::DisabledFormat(Self::Yaml.into(), None)),
+
+            #[cfg(feature = "ron")]
+            Self::Ron => {
+                let config = ron::ser::PrettyConfig::default();
+                let ron = ron_options()
+                    .to_string_pretty(value, config)
+                    .map_err(Error::SerializeRon)?;
+                // Tuples are pretty-printed on a single line
+                if ron != "()" && !ron.starts_with("(\n") {
+                    return Err(Error::SerializeRon(serde::ser::Error::custom(
+                        "frontmatter must be a RON struct",
+                    )));
+                }
+                if ron == "()" {
+                    // The opening parenthesis has to be on its own line
+                    "(\n)".into()
+                } else {
+                    ron
+                }
+            }
+            #[cfg(not(feature = "ron"))]
+            Self::Ron => return Err(Error::DisabledFormat(Self::Ron.into(), None)),
+
+            Self::Mmd | Self::Custom(_) => return Err(Error::NotSerializable((*self).into())),
+        };
+        if !matter.is_empty() && !matter.ends_with('\n') {
+            matter.push('\n');
         }
+
+        let closing_delimiter = self.delimiter().1;
+        let payload = match self {
+            // The last line of JSON and RON is the closing delimiter itself
+            Self::Json | Self::Json5 | Self::Ron => matter
+                .trim_end()
+                .strip_suffix(closing_delimiter)
+                .unwrap_or_default(),
+            _ => &matter,
+        };
+        if LineSpan::new(payload).any(|span| span.line == closing_delimiter) {
+            return Err(Error::DelimiterInFrontmatter((*self).into()));
+        }
+        Ok(matter)
     }
 
-    fn delimiter(&self) -> (&'static str, &'static str) {
+    /// Returns the opening and closing delimiters of the format.
+    pub fn delimiter(&self) -> (&'static str, &'static str) {
         match self {
-            Self::Json => ("{", "}"),
+            Self::Json | Self::Json5 => ("{", "}"),
             Self::Toml => ("+++", "+++"),
             Self::Yaml => ("---", "---"),
+            Self::Ron => ("(", ")"),
+            // Metadata lines aren't delimited, but a blank line ends them
+            Self::Mmd => ("", ""),
+            #[cfg(feature = "_serde")]
+            Self::Custom(format) => format.delimiters(),
         }
     }
 }
@@ -215,38 +1012,34 @@ struct LineSpan<'a> {
 
 impl<'a> LineSpan<'a> {
     fn new(s: &'a str) -> impl Iterator<Item = LineSpan<'a>> + 'a {
-        let bytes = s.as_bytes();
         let mut pos = 0;
         std::iter::from_fn(move || {
-            if pos >= bytes.len() {
+            if pos >= s.len() {
                 return None;
             }
             let start = pos;
-            let mut i = start;
-            while i < bytes.len() && bytes[i] != b'\n' && bytes[i] != b'\r' {
-                i += 1;
-            }
-            let line_end = i;
-            if i < bytes.len() && bytes[i] == b'\r' {
-                i += 1;
-                if i < bytes.len() && bytes[i] == b'\n' {
-                    i += 1;
-                }
-            } else if i < bytes.len() && bytes[i] == b'\n' {
-                i += 1;
-            }
-            let line = &s[start..line_end];
-            let next_start = i;
-            pos = i;
+            let (line_end, next_start) = line_break(&s.as_bytes()[start..])
+                .map_or((s.len(), s.len()), |(end, next)| {
+                    (start + end, start + next)
+                });
+            pos = next_start;
             Some(LineSpan {
                 start,
                 next_start,
-                line,
+                line: &s[start..line_end],
             })
         })
     }
 }
 
+/// Finds the first line break, `\n`, `\r\n` or a lone `\r`, returning the end
+/// of the line and the start of the next one.
+fn line_break(bytes: &[u8]) -> Option<(usize, usize)> {
+    let end = bytes.iter().position(|&b| b == b'\n' || b == b'\r')?;
+    let crlf = bytes[end] == b'\r' && bytes.get(end + 1) == Some(&b'\n');
+    Some((end, end + 1 + usize::from(crlf)))
+}
+
 #[cfg(test)]
 mod test_line_span {
     use super::*;
@@ -301,7 +1094,7 @@ mod test_split {
         let result = split(input);
         assert!(matches!(
             result.unwrap_err(),
-            Error::AbsentClosingDelimiter("JSON")
+            Error::AbsentClosingDelimiter("JSON", ..)
         ));
     }
 
@@ -311,7 +1104,7 @@ mod test_split {
         let result = split(input);
         assert!(matches!(
             result.unwrap_err(),
-            Error::AbsentClosingDelimiter("TOML")
+            Error::AbsentClosingDelimiter("TOML", ..)
         ));
     }
 
@@ -321,16 +1114,26 @@ mod test_split {
         let result = split(input);
         assert!(matches!(
             result.unwrap_err(),
-            Error::AbsentClosingDelimiter("YAML")
+            Error::AbsentClosingDelimiter("YAML", ..)
         ));
     }
 
+    #[cfg(not(feature = "ron"))]
+    #[test]
+    fn parenthesis_without_ron() {
+        for input in ["(\nsee below\n)\nbody", "(\nsome aside\n\nbody"] {
+            let (frontmatter, body) = split(input).unwrap();
+            assert!(frontmatter.is_none());
+            assert_eq!(body, input);
+        }
+    }
+
     #[test]
     fn json_singleline() {
         let input = "{\n\t\"foo\": \"bar\"\n}\nhello world";
         let (frontmatter, body) = split(input).unwrap();
-        assert_eq!(frontmatter.unwrap().1, "{\n\t\"foo\": \"bar\"\n}\n");
-        assert_eq!(frontmatter.unwrap().0, FrontmatterFormat::Json);
+        assert_eq!(frontmatter.unwrap().as_str(), "{\n\t\"foo\": \"bar\"\n}\n");
+        assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Json);
         assert_eq!(body, "hello world");
     }
 
@@ -339,10 +1142,10 @@ mod test_split {
         let input = "{\n\t\"foo\": \"bar\",\n\t\"baz\": 1\n}\nhello world";
         let (frontmatter, body) = split(input).unwrap();
         assert_eq!(
-            frontmatter.unwrap().1,
+            frontmatter.unwrap().as_str(),
             "{\n\t\"foo\": \"bar\",\n\t\"baz\": 1\n}\n"
         );
-        assert_eq!(frontmatter.unwrap().0, FrontmatterFormat::Json);
+        assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Json);
         assert_eq!(body, "hello world");
     }
 
@@ -350,8 +1153,8 @@ mod test_split {
     fn toml_singleline() {
         let input = "+++\nfoo = \"bar\"\n+++\nhello world";
         let (frontmatter, body) = split(input).unwrap();
-        assert_eq!(frontmatter.unwrap().1, "foo = \"bar\"\n");
-        assert_eq!(frontmatter.unwrap().0, FrontmatterFormat::Toml);
+        assert_eq!(frontmatter.unwrap().as_str(), "foo = \"bar\"\n");
+        assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Toml);
         assert_eq!(body, "hello world");
     }
 
@@ -359,8 +1162,8 @@ mod test_split {
     fn toml_multiline() {
         let input = "+++\nfoo = \"bar\"\nbaz = 1\n+++\nhello world";
         let (frontmatter, body) = split(input).unwrap();
-        assert_eq!(frontmatter.unwrap().1, "foo = \"bar\"\nbaz = 1\n");
-        assert_eq!(frontmatter.unwrap().0, FrontmatterFormat::Toml);
+        assert_eq!(frontmatter.unwrap().as_str(), "foo = \"bar\"\nbaz = 1\n");
+        assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Toml);
         assert_eq!(body, "hello world");
     }
 
@@ -368,8 +1171,8 @@ mod test_split {
     fn yaml_singleline() {
         let input = "---\nfoo: bar\n---\nhello world";
         let (frontmatter, body) = split(input).unwrap();
-        assert_eq!(frontmatter.unwrap().1, "foo: bar\n");
-        assert_eq!(frontmatter.unwrap().0, FrontmatterFormat::Yaml);
+        assert_eq!(frontmatter.unwrap().as_str(), "foo: bar\n");
+        assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Yaml);
         assert_eq!(body, "hello world");
     }
 
@@ -377,13 +1180,55 @@ mod test_split {
     fn yaml_multiline() {
         let input = "---\nfoo: bar\nbaz: 1\n---\nhello world";
         let (frontmatter, body) = split(input).unwrap();
-        assert_eq!(frontmatter.unwrap().1, "foo: bar\nbaz: 1\n");
-        assert_eq!(frontmatter.unwrap().0, FrontmatterFormat::Yaml);
+        assert_eq!(frontmatter.unwrap().as_str(), "foo: bar\nbaz: 1\n");
+        assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Yaml);
         assert_eq!(body, "hello world");
     }
+
+    #[test]
+    fn span_points_into_original_document() {
+        let input = "\n  \n---\nfoo: bar\n---\nhello world";
+        let (frontmatter, _) = split(input).unwrap();
+        let frontmatter = frontmatter.unwrap();
+        assert_eq!(frontmatter.span(), 8..17);
+        assert_eq!(&input[frontmatter.span()], "foo: bar\n");
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn ron() {
+        let input = "(\n    foo: \"bar\",\n)\nhello world";
+        let (frontmatter, body) = split(input).unwrap();
+        assert_eq!(frontmatter.unwrap().as_str(), "(\n    foo: \"bar\",\n)\n");
+        assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Ron);
+        assert_eq!(body, "hello world");
+    }
+
+    #[test]
+    fn json_span_includes_brackets() {
+        let input = " {\n\"foo\": 1\n}\nhello world";
+        let (frontmatter, _) = split(input).unwrap();
+        assert_eq!(&input[frontmatter.unwrap().span()], "{\n\"foo\": 1\n}\n");
+    }
+
+    #[test]
+    fn unclosed_delimiter_span() {
+        let input = "\n\n  +++\nfoo = \"bar\"";
+        let err = split(input).unwrap_err();
+        let span = err.span().unwrap();
+        assert_eq!(&input[span.range()], "+++");
+        assert_eq!((span.line, span.column), (3, 3));
+        assert_eq!(
+            err.to_string(),
+            "absent closing TOML delimiter for the opening one at line 3, column 3"
+        );
+    }
 }
 
-#[cfg(all(test, any(feature = "json", feature = "toml", feature = "yaml")))]
+#[cfg(all(
+    test,
+    any(feature = "json", feature = "toml", feature = "yaml", feature = "ron")
+))]
 mod test_parse {
     use serde::Deserialize;
 
@@ -405,6 +1250,19 @@ mod test_parse {
     const EMPTY_DOCUMENT: &str = "";
     const DOCUMENT_WITHOUT_FRONTMATTER: &str = "hello world";
 
+    #[derive(Debug, Deserialize)]
+    #[expect(dead_code)]
+    struct NestedFrontmatter {
+        authors: Vec<Author>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[expect(dead_code)]
+    struct Author {
+        name: String,
+        email: Option<String>,
+    }
+
     const EMPTY_FRONTMATTER: EmptyFrontmatter = EmptyFrontmatter {};
     const OPTIONAL_FRONTMATTER_SOME: OptionalFrontmatter = OptionalFrontmatter { foo: Some(true) };
     const OPTIONAL_FRONTMATTER_NONE: OptionalFrontmatter = OptionalFrontmatter { foo: None };
@@ -497,6 +1355,40 @@ mod test_parse {
             let result = parse::<RequiredFrontmatter>(INVALID_TYPE);
             assert!(matches!(result.unwrap_err(), Error::DeserializeJson(..)));
         }
+
+        #[test]
+        fn invalid_type_location() {
+            let input = "{\n  \"foo\": 0\n}\n";
+            let err = parse::<OptionalFrontmatter>(input).unwrap_err();
+            assert!(matches!(err, Error::DeserializeJson(..)));
+            assert_eq!(err.span().unwrap().line, 2);
+        }
+
+        #[test]
+        fn trailing_characters() {
+            let err = parse::<OptionalFrontmatter>("{\n} 1\n}\n").unwrap_err();
+            assert!(matches!(err, Error::InvalidJson(..)));
+        }
+
+        #[test]
+        fn invalid_type_path() {
+            let input = r#"{
+  "authors": [{"name": "a"}, {"name": "b", "email": 1}]
+}"#;
+            let err = parse::<NestedFrontmatter>(input).unwrap_err();
+            assert!(matches!(err, Error::DeserializeJson(..)));
+            assert_eq!(err.path(), Some("authors[1].email"));
+        }
+
+        #[test]
+        fn invalid_syntax_location() {
+            let input = "\n{\n  \"foo\": @\n}\n";
+            let err = parse::<OptionalFrontmatter>(input).unwrap_err();
+            let span = err.span().unwrap();
+            assert_eq!(&input[span.range()], "@");
+            assert_eq!((span.line, span.column), (3, 10));
+            assert_eq!(err.to_string(), "invalid JSON syntax at line 3, column 10");
+        }
     }
 
     #[cfg(feature = "toml")]
@@ -591,6 +1483,44 @@ mod test_parse {
             let result = parse::<RequiredFrontmatter>(INVALID_TYPE);
             assert!(matches!(result.unwrap_err(), Error::DeserializeToml(..)));
         }
+
+        #[test]
+        fn invalid_syntax_location() {
+            let input = "  +++\nfoo = true\nbar = \n+++\n";
+            let err = parse::<OptionalFrontmatter>(input).unwrap_err();
+            let span = err.span().unwrap();
+            assert_eq!((span.line, span.column), (3, 7));
+            assert!(matches!(err, Error::InvalidToml(..)));
+        }
+
+        #[test]
+        fn invalid_type_location() {
+            let input = "+++\nfoo = 1\n+++\n";
+            let err = parse::<OptionalFrontmatter>(input).unwrap_err();
+            assert_eq!(&input[err.span().unwrap().range()], "1");
+        }
+
+        #[test]
+        fn invalid_type_path() {
+            let input =
+                "+++\n[[authors]]\nname = \"a\"\n[[authors]]\nname = \"b\"\nemail = 1\n+++\n";
+            let err = parse::<NestedFrontmatter>(input).unwrap_err();
+            assert_eq!(err.path(), Some("authors[1].email"));
+            assert!(err.to_string().starts_with(
+                "couldn't deserialize TOML field `authors[1].email` at line 6, column 9"
+            ));
+        }
+
+        #[test]
+        fn root_path() {
+            let err = parse::<RequiredFrontmatter>(
+                "+++
++++
+",
+            )
+            .unwrap_err();
+            assert_eq!(err.path(), Some("."));
+        }
     }
 
     #[cfg(feature = "yaml")]
@@ -685,5 +1615,421 @@ mod test_parse {
             let result = parse::<RequiredFrontmatter>(INVALID_TYPE);
             assert!(matches!(result.unwrap_err(), Error::DeserializeYaml(..)));
         }
+
+        #[test]
+        fn invalid_type_location() {
+            let input = "---\nbar: 1\nfoo: 123\n---\n";
+            let err = parse::<OptionalFrontmatter>(input).unwrap_err();
+            assert!(matches!(err, Error::DeserializeYaml(..)));
+            let span = err.span().unwrap();
+            assert_eq!((span.line, span.column), (3, 6));
+        }
+
+        #[test]
+        fn invalid_type_path() {
+            let input = "---\nauthors:\n- name: a\n- name: b\n  email: [1]\n---\n";
+            let err = parse::<NestedFrontmatter>(input).unwrap_err();
+            assert!(matches!(err, Error::DeserializeYaml(..)));
+            assert_eq!(err.path(), Some("authors[1].email"));
+        }
+
+        #[test]
+        fn invalid_syntax_location() {
+            let input = "---\nfoo: bar\n  baz: [\n---\n";
+            let err = parse::<OptionalFrontmatter>(input).unwrap_err();
+            let span = err.span().unwrap();
+            assert_eq!(span.line, 3);
+            assert!(matches!(err, Error::InvalidYaml(..)));
+        }
+    }
+
+    #[cfg(feature = "json5")]
+    mod json5 {
+        use super::*;
+
+        const VALID_DOCUMENT: &str = "{\n  // comment\n  foo: true,\n}\nhello world";
+        const INVALID_SYNTAX: &str = "{\n  foo: true true\n}\n";
+        const INVALID_TYPE: &str = "{\n  foo: 123,\n}\n";
+
+        fn parse<T: serde::de::DeserializeOwned>(content: &str) -> Result<(T, &str), Error> {
+            Parser::new().json5(true).parse(content)
+        }
+
+        #[test]
+        fn optional_frontmatter_in_valid_document() {
+            let (frontmatter, body) = parse::<OptionalFrontmatter>(VALID_DOCUMENT).unwrap();
+            assert_eq!(frontmatter, OPTIONAL_FRONTMATTER_SOME);
+            assert_eq!(body, "hello world");
+        }
+
+        #[test]
+        fn required_frontmatter_in_valid_document() {
+            let (frontmatter, body) = parse::<RequiredFrontmatter>(VALID_DOCUMENT).unwrap();
+            assert_eq!(frontmatter, REQUIRED_FRONTMATTER);
+            assert_eq!(body, "hello world");
+        }
+
+        #[test]
+        fn optional_frontmatter_in_document_without_frontmatter() {
+            let (frontmatter, body) =
+                parse::<OptionalFrontmatter>(DOCUMENT_WITHOUT_FRONTMATTER).unwrap();
+            assert_eq!(frontmatter, OPTIONAL_FRONTMATTER_NONE);
+            assert_eq!(body, DOCUMENT_WITHOUT_FRONTMATTER);
+        }
+
+        #[test]
+        fn strict_json_by_default() {
+            let result = super::parse::<OptionalFrontmatter>(VALID_DOCUMENT);
+            assert!(matches!(result.unwrap_err(), Error::InvalidJson(..)));
+        }
+
+        #[test]
+        fn invalid_syntax() {
+            let err = parse::<OptionalFrontmatter>(INVALID_SYNTAX).unwrap_err();
+            assert!(matches!(err, Error::InvalidJson5(..)));
+            assert_eq!(err.span().unwrap().line, 2);
+        }
+
+        #[test]
+        fn invalid_type() {
+            let err = parse::<RequiredFrontmatter>(INVALID_TYPE).unwrap_err();
+            assert!(matches!(err, Error::DeserializeJson5(..)));
+            assert_eq!(err.path(), Some("foo"));
+            assert_eq!(err.span().unwrap().line, 2);
+        }
+
+        #[test]
+        fn round_trip() {
+            let doc = to_string(
+                &serde_json::json!({ "foo": true }),
+                FrontmatterFormat::Json5,
+                "",
+            )
+            .unwrap();
+            assert_eq!(doc, "{\n  \"foo\": true\n}\n");
+            let (frontmatter, _) = parse::<RequiredFrontmatter>(&doc).unwrap();
+            assert_eq!(frontmatter, REQUIRED_FRONTMATTER);
+        }
+    }
+
+    #[cfg(feature = "ron")]
+    mod ron {
+        use super::*;
+
+        const VALID_DOCUMENT: &str = "(\n    foo: true,\n)\nhello world";
+        const INVALID_SYNTAX: &str = "(\n    foo: true true\n)\n";
+        const INVALID_TYPE: &str = "(\n    foo: 123,\n)\n";
+
+        #[cfg(not(any(feature = "json", feature = "toml", feature = "yaml")))]
+        mod only {
+            use super::*;
+
+            #[test]
+            fn empty_frontmatter_in_empty_document() {
+                let (frontmatter, body) = parse::<EmptyFrontmatter>(EMPTY_DOCUMENT).unwrap();
+                assert_eq!(frontmatter, EmptyFrontmatter {});
+                assert_eq!(body, "");
+            }
+
+            #[test]
+            fn empty_frontmatter_in_document_without_frontmatter() {
+                let (frontmatter, body) =
+                    parse::<EmptyFrontmatter>(DOCUMENT_WITHOUT_FRONTMATTER).unwrap();
+                assert_eq!(frontmatter, EMPTY_FRONTMATTER);
+                assert_eq!(body, DOCUMENT_WITHOUT_FRONTMATTER);
+            }
+
+            #[test]
+            fn optional_frontmatter_in_empty_document() {
+                let (frontmatter, body) = parse::<OptionalFrontmatter>(EMPTY_DOCUMENT).unwrap();
+                assert_eq!(frontmatter.foo, None);
+                assert_eq!(body, "");
+            }
+
+            #[test]
+            fn required_frontmatter_in_empty_document() {
+                let result = parse::<RequiredFrontmatter>(EMPTY_DOCUMENT);
+                assert!(matches!(result.unwrap_err(), Error::DeserializeRon(..)));
+            }
+
+            #[test]
+            fn optional_frontmatter_in_document_without_frontmatter() {
+                let (frontmatter, body) =
+                    parse::<OptionalFrontmatter>(DOCUMENT_WITHOUT_FRONTMATTER).unwrap();
+                assert_eq!(frontmatter, OPTIONAL_FRONTMATTER_NONE);
+                assert_eq!(body, DOCUMENT_WITHOUT_FRONTMATTER);
+            }
+        }
+
+        #[test]
+        fn optional_frontmatter_in_valid_document() {
+            let (frontmatter, body) = parse::<OptionalFrontmatter>(VALID_DOCUMENT).unwrap();
+            assert_eq!(frontmatter, OPTIONAL_FRONTMATTER_SOME);
+            assert_eq!(body, "hello world");
+        }
+
+        #[test]
+        fn required_frontmatter_in_valid_document() {
+            let (frontmatter, body) = parse::<RequiredFrontmatter>(VALID_DOCUMENT).unwrap();
+            assert_eq!(frontmatter, REQUIRED_FRONTMATTER);
+            assert_eq!(body, "hello world");
+        }
+
+        #[test]
+        fn invalid_syntax() {
+            let err = parse::<OptionalFrontmatter>(INVALID_SYNTAX).unwrap_err();
+            assert!(matches!(err, Error::InvalidRon(..)));
+            assert_eq!(err.span().unwrap().line, 2);
+        }
+
+        #[test]
+        fn invalid_type() {
+            let err = parse::<RequiredFrontmatter>(INVALID_TYPE).unwrap_err();
+            assert!(matches!(err, Error::DeserializeRon(..)));
+            assert_eq!(err.path(), Some("foo"));
+            assert_eq!(err.span().unwrap().line, 2);
+        }
+
+        #[test]
+        fn typed_metadata() {
+            #[derive(Debug, PartialEq, Deserialize)]
+            enum Kind {
+                Post { weight: u8 },
+            }
+
+            #[derive(Debug, PartialEq, Deserialize)]
+            struct Frontmatter {
+                kind: Kind,
+                size: (u32, u32),
+            }
+
+            let doc = "(\n    kind: Post(weight: 1),\n    size: (1, 2),\n)\n";
+            let (frontmatter, _) = parse::<Frontmatter>(doc).unwrap();
+            assert_eq!(
+                frontmatter,
+                Frontmatter {
+                    kind: Kind::Post { weight: 1 },
+                    size: (1, 2),
+                }
+            );
+        }
+    }
+
+    #[cfg(not(feature = "toml"))]
+    #[test]
+    fn disabled_format_location() {
+        let input = "\n+++\nfoo = true\n+++\n";
+        let err = parse::<OptionalFrontmatter>(input).unwrap_err();
+        assert!(matches!(err, Error::DisabledFormat("TOML", _)));
+        assert_eq!(&input[err.span().unwrap().range()], "+++");
+    }
+}
+
+#[cfg(all(
+    test,
+    any(feature = "json", feature = "toml", feature = "yaml", feature = "ron")
+))]
+mod test_parse_borrowed {
+    use std::borrow::Cow;
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Frontmatter<'a> {
+        title: &'a str,
+        #[serde(borrow)]
+        summary: Cow<'a, str>,
+    }
+
+    fn assert_borrowed(doc: &str) {
+        let (frontmatter, body) = parse_borrowed::<Frontmatter<'_>>(doc).unwrap();
+        assert_eq!(frontmatter.title, "Hello");
+        assert!(
+            doc.as_bytes()
+                .as_ptr_range()
+                .contains(&frontmatter.title.as_ptr())
+        );
+        assert_eq!(frontmatter.summary, "a\"b");
+        assert!(matches!(frontmatter.summary, Cow::Owned(_)));
+        assert_eq!(body, "World");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json() {
+        assert_borrowed("{\n  \"title\": \"Hello\",\n  \"summary\": \"a\\\"b\"\n}\nWorld");
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml() {
+        assert_borrowed("+++\ntitle = \"Hello\"\nsummary = \"a\\\"b\"\n+++\nWorld");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml() {
+        assert_borrowed("---\ntitle: Hello\nsummary: \"a\\\"b\"\n---\nWorld");
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn ron() {
+        assert_borrowed("(\n    title: \"Hello\",\n    summary: \"a\\\"b\",\n)\nWorld");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn escaped_str_is_not_borrowable() {
+        let doc = "{\n  \"title\": \"a\\\"b\",\n  \"summary\": \"\"\n}\n";
+        let err = parse_borrowed::<Frontmatter<'_>>(doc).unwrap_err();
+        assert_eq!(err.path(), Some("title"));
+    }
+}
+
+#[cfg(all(
+    test,
+    any(feature = "json", feature = "toml", feature = "yaml", feature = "ron")
+))]
+mod test_to_string {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Frontmatter {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    const BODY: &str = "\n# Hello\n\nworld\r\n";
+
+    fn frontmatter() -> Frontmatter {
+        Frontmatter {
+            title: "Hello".into(),
+            tags: vec!["a".into(), "b".into()],
+        }
+    }
+
+    fn assert_round_trip(format: FrontmatterFormat) {
+        let doc = to_string(&frontmatter(), format, BODY).unwrap();
+        let (split_matter, _) = split(&doc).unwrap();
+        assert_eq!(split_matter.unwrap().format(), format);
+        let (parsed, body) = parse::<Frontmatter>(&doc).unwrap();
+        assert_eq!(parsed, frontmatter());
+        assert_eq!(body, BODY);
+    }
+
+    #[cfg(feature = "json")]
+    mod json {
+        use super::*;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct EmptyFrontmatter {}
+
+        #[test]
+        fn round_trip() {
+            assert_round_trip(FrontmatterFormat::Json);
+        }
+
+        #[test]
+        fn empty_object() {
+            let doc = to_string(&EmptyFrontmatter {}, FrontmatterFormat::Json, BODY).unwrap();
+            assert_eq!(doc, format!("{{\n}}\n{BODY}"));
+            let (parsed, body) = parse::<EmptyFrontmatter>(&doc).unwrap();
+            assert_eq!(parsed, EmptyFrontmatter {});
+            assert_eq!(body, BODY);
+        }
+
+        #[test]
+        fn not_an_object() {
+            let result = to_string(&[1, 2], FrontmatterFormat::Json, BODY);
+            assert!(matches!(result.unwrap_err(), Error::SerializeJson(..)));
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    mod toml {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            assert_round_trip(FrontmatterFormat::Toml);
+        }
+
+        #[test]
+        fn closing_delimiter_inside() {
+            let frontmatter = Frontmatter {
+                title: "a\n+++\nb".into(),
+                tags: vec![],
+            };
+            let result = to_string(&frontmatter, FrontmatterFormat::Toml, BODY);
+            assert!(matches!(
+                result.unwrap_err(),
+                Error::DelimiterInFrontmatter("TOML")
+            ));
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    mod yaml {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            assert_round_trip(FrontmatterFormat::Yaml);
+        }
+
+        #[test]
+        fn write_into_writer() {
+            let mut buf = Vec::new();
+            write(&mut buf, &frontmatter(), FrontmatterFormat::Yaml, BODY).unwrap();
+            assert_eq!(
+                String::from_utf8(buf).unwrap(),
+                format!("---\ntitle: Hello\ntags:\n- a\n- b\n---\n{BODY}")
+            );
+        }
+
+        #[test]
+        fn multiline_string_with_delimiter() {
+            let frontmatter = Frontmatter {
+                title: "a\n---\nb".into(),
+                tags: vec![],
+            };
+            let doc = to_string(&frontmatter, FrontmatterFormat::Yaml, BODY).unwrap();
+            let (parsed, body) = parse::<Frontmatter>(&doc).unwrap();
+            assert_eq!(parsed, frontmatter);
+            assert_eq!(body, BODY);
+        }
+    }
+
+    #[cfg(feature = "ron")]
+    mod ron {
+        use super::*;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct EmptyFrontmatter {}
+
+        #[test]
+        fn round_trip() {
+            assert_round_trip(FrontmatterFormat::Ron);
+        }
+
+        #[test]
+        fn empty_struct() {
+            let doc = to_string(&EmptyFrontmatter {}, FrontmatterFormat::Ron, BODY).unwrap();
+            assert_eq!(doc, format!("(\n)\n{BODY}"));
+            let (parsed, body) = parse::<EmptyFrontmatter>(&doc).unwrap();
+            assert_eq!(parsed, EmptyFrontmatter {});
+            assert_eq!(body, BODY);
+        }
+
+        #[test]
+        fn not_a_struct() {
+            let result = to_string(&(1, 2), FrontmatterFormat::Ron, BODY);
+            assert!(matches!(result.unwrap_err(), Error::SerializeRon(..)));
+        }
     }
 }
@@ -0,0 +1,132 @@
+use std::ops::Range;
+
+use crate::{LineSpan, value::Value};
+
+/// Returns `true` if the line is a `Key: value` line.
+pub(crate) fn is_metadata_line(line: &str) -> bool {
+    key_value(line).is_some()
+}
+
+/// Parses MultiMarkdown metadata, `Key: value` lines ended by a blank line,
+/// into a table of strings and lists.
+///
+/// Keys are lowercased with spaces and hyphens replaced by underscores, so
+/// `Base Header Level` becomes `base_header_level`. A value continued on
+/// indented lines, or a key repeated on several lines, becomes a list of the
+/// lines. On failure returns the range of the offending line.
+pub(crate) fn parse(matter: &str) -> Result<Value, (&'static str, Range<usize>)> {
+    let mut table: Vec<(String, Vec<String>)> = Vec::new();
+    let mut current: Option<usize> = None;
+    for span in LineSpan::new(matter) {
+        let line = span.line;
+        if line.trim().is_empty() {
+            break;
+        }
+        if line.starts_with(char::is_whitespace) {
+            let Some(i) = current else {
+                return Err((
+                    "continuation line without a key",
+                    span.start..span.next_start,
+                ));
+            };
+            table[i].1.push(line.trim().into());
+            continue;
+        }
+        let Some((key, value)) = key_value(line) else {
+            return Err(("expected `Key: value`", span.start..span.next_start));
+        };
+        let key = normalize_key(key);
+        let i = table
+            .iter()
+            .position(|(k, _)| *k == key)
+            .unwrap_or_else(|| {
+                table.push((key, Vec::new()));
+                table.len() - 1
+            });
+        table[i].1.push(value.trim().into());
+        current = Some(i);
+    }
+    Ok(Value::Table(
+        table
+            .into_iter()
+            .map(|(key, mut lines)| {
+                let value = if lines.len() == 1 {
+                    Value::String(lines.remove(0))
+                } else {
+                    // The value of a key followed by indented lines is empty
+                    lines.retain(|line| !line.is_empty());
+                    Value::Array(lines.into_iter().map(Value::String).collect())
+                };
+                (key, value)
+            })
+            .collect(),
+    ))
+}
+
+/// Splits a `Key: value` line, keys consist of alphanumerics, spaces,
+/// hyphens and underscores.
+fn key_value(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    let valid_key = key.starts_with(char::is_alphanumeric)
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'));
+    // Rules out URLs, e.g. `https://example.com`
+    let valid_value = value.is_empty() || value.starts_with(char::is_whitespace);
+    (valid_key && valid_value).then_some((key, value))
+}
+
+fn normalize_key(key: &str) -> String {
+    key.trim()
+        .chars()
+        .map(|c| match c {
+            ' ' | '-' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(s: &str) -> Value {
+        Value::String(s.into())
+    }
+
+    #[test]
+    fn metadata_line() {
+        assert!(is_metadata_line("Title: Hello"));
+        assert!(is_metadata_line("Base Header Level: 2"));
+        assert!(is_metadata_line("Authors:"));
+        assert!(!is_metadata_line("https://example.com"));
+        assert!(!is_metadata_line("# Title: Hello"));
+        assert!(!is_metadata_line("Hello world"));
+    }
+
+    #[test]
+    fn strings_and_lists() {
+        let matter =
+            "Title: Hello\nBase Header Level: 2\nAuthors:\n    Alice\n    Bob\nTag: a\nTag: b\n";
+        let value = parse(matter).unwrap();
+        assert_eq!(value.get("title"), Some(&string("Hello")));
+        assert_eq!(value.get("base_header_level"), Some(&string("2")));
+        assert_eq!(
+            value.get("authors"),
+            Some(&Value::Array(vec![string("Alice"), string("Bob")]))
+        );
+        assert_eq!(
+            value.get("tag"),
+            Some(&Value::Array(vec![string("a"), string("b")]))
+        );
+    }
+
+    #[test]
+    fn invalid_line() {
+        let matter = "Title: Hello\nnot metadata\n";
+        assert_eq!(
+            parse(matter).unwrap_err(),
+            ("expected `Key: value`", 13..26)
+        );
+    }
+}
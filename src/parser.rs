@@ -0,0 +1,1726 @@
+#[cfg(feature = "_serde")]
+use std::io::{self, BufRead};
+use std::ops::Range;
+
+#[cfg(all(feature = "async", feature = "_serde"))]
+use futures_util::{AsyncBufRead, AsyncBufReadExt};
+
+#[cfg(feature = "_serde")]
+use crate::{BodyReader, Format, line_break};
+use crate::{Error, FrontmatterFormat, LineSpan, Span, SplitFrontmatter};
+
+/// A configurable frontmatter parser.
+///
+/// The free functions, e.g. [`split`](crate::split) and
+/// [`parse`](crate::parse), use the default configuration, the parser allows
+/// to change it.
+#[derive(Debug, Clone)]
+pub struct Parser {
+    #[cfg(feature = "_serde")]
+    formats: Vec<&'static dyn Format>,
+    delimiters: Vec<(FrontmatterFormat, &'static str, &'static str)>,
+    #[cfg(feature = "json5")]
+    json5: bool,
+    #[cfg(feature = "mmd")]
+    mmd: bool,
+    pandoc_yaml: bool,
+    html_comment: bool,
+    fenced_code: bool,
+    format_tags: bool,
+    require_frontmatter: bool,
+    allowed_formats: Option<Vec<FrontmatterFormat>>,
+    trim_leading_whitespace: bool,
+    #[cfg(feature = "_serde")]
+    default_format: Option<FrontmatterFormat>,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser {
+    /// Creates a parser detecting the built-in formats.
+    pub const fn new() -> Self {
+        Self {
+            #[cfg(feature = "_serde")]
+            formats: Vec::new(),
+            delimiters: Vec::new(),
+            #[cfg(feature = "json5")]
+            json5: false,
+            #[cfg(feature = "mmd")]
+            mmd: false,
+            pandoc_yaml: false,
+            html_comment: false,
+            fenced_code: false,
+            format_tags: true,
+            require_frontmatter: false,
+            allowed_formats: None,
+            trim_leading_whitespace: true,
+            #[cfg(feature = "_serde")]
+            default_format: None,
+        }
+    }
+
+    /// Registers a custom format.
+    ///
+    /// Custom formats are detected before the built-in ones in the order of
+    /// registration, so they can take over delimiters of the built-in formats.
+    #[cfg(feature = "_serde")]
+    pub fn format(mut self, format: &'static dyn Format) -> Self {
+        self.formats.push(format);
+        self
+    }
+
+    /// Registers alternative opening and closing delimiters of a format.
+    ///
+    /// Like custom formats, the delimiters are detected before the built-in
+    /// ones in the order of registration. They frame the frontmatter, so they
+    /// aren't a part of it even for JSON.
+    ///
+    /// ```
+    /// use markdown_frontmatter::{FrontmatterFormat, Parser};
+    ///
+    /// let doc = ";;;\n{\"title\": \"Hello\"}\n;;;\nWorld";
+    ///
+    /// let parser = Parser::new().delimiters(FrontmatterFormat::Json, ";;;", ";;;");
+    /// let (frontmatter, body) = parser.split(doc).unwrap();
+    /// let frontmatter = frontmatter.unwrap();
+    /// assert_eq!(frontmatter.format(), FrontmatterFormat::Json);
+    /// assert_eq!(frontmatter.as_str(), "{\"title\": \"Hello\"}\n");
+    /// assert_eq!(body, "World");
+    /// ```
+    pub fn delimiters(
+        mut self,
+        format: FrontmatterFormat,
+        opening: &'static str,
+        closing: &'static str,
+    ) -> Self {
+        self.delimiters.push((format, opening, closing));
+        self
+    }
+
+    /// Parses `{...}` frontmatter as JSON5, allowing comments, trailing commas
+    /// and unquoted keys.
+    ///
+    /// ```
+    /// use markdown_frontmatter::{FrontmatterFormat, Parser};
+    ///
+    /// let doc = "{\n  // Draft\n  title: 'Hello',\n}\nWorld";
+    ///
+    /// let parser = Parser::new().json5(true);
+    /// let (frontmatter, _) = parser.split(doc).unwrap();
+    /// assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Json5);
+    ///
+    /// let (frontmatter, body) = parser.parse::<serde_json::Value>(doc).unwrap();
+    /// assert_eq!(frontmatter["title"], "Hello");
+    /// assert_eq!(body, "World");
+    /// ```
+    #[cfg(feature = "json5")]
+    pub fn json5(mut self, enabled: bool) -> Self {
+        self.json5 = enabled;
+        self
+    }
+
+    /// Detects MultiMarkdown metadata, `Key: value` lines at the top of a
+    /// document ended by a blank line.
+    ///
+    /// It's opt-in as any document starting with a `Key: value` line is
+    /// detected. Values are strings, or lists of strings for values continued
+    /// on indented lines and repeated keys. Keys are lowercased with spaces and
+    /// hyphens replaced by underscores, e.g. `Base Header Level` becomes
+    /// `base_header_level`.
+    ///
+    /// ```
+    /// use markdown_frontmatter::{FrontmatterFormat, Parser};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Frontmatter {
+    ///     title: String,
+    ///     authors: Vec<String>,
+    /// }
+    ///
+    /// let doc = "Title: Hello\nAuthors:\n    Alice\n    Bob\n\nWorld";
+    ///
+    /// let (frontmatter, body) = Parser::new().mmd(true).parse::<Frontmatter>(doc).unwrap();
+    /// assert_eq!(frontmatter.title, "Hello");
+    /// assert_eq!(frontmatter.authors, ["Alice", "Bob"]);
+    /// assert_eq!(body, "World");
+    /// ```
+    #[cfg(feature = "mmd")]
+    pub const fn mmd(mut self, enabled: bool) -> Self {
+        self.mmd = enabled;
+        self
+    }
+
+    /// Accepts `...` as the closing line of YAML frontmatter, as Pandoc does
+    /// for YAML metadata blocks.
+    ///
+    /// ```
+    /// use markdown_frontmatter::Parser;
+    ///
+    /// let doc = "---\ntitle: Hello\n...\nWorld";
+    ///
+    /// let (frontmatter, body) = Parser::new().pandoc_yaml(true).split(doc).unwrap();
+    /// assert_eq!(frontmatter.unwrap().as_str(), "title: Hello\n");
+    /// assert_eq!(body, "World");
+    /// ```
+    pub const fn pandoc_yaml(mut self, enabled: bool) -> Self {
+        self.pandoc_yaml = enabled;
+        self
+    }
+
+    /// Detects frontmatter wrapped in a leading HTML comment, hiding it from
+    /// Markdown renderers.
+    ///
+    /// The comment markers can be on their own lines or on the delimiter
+    /// lines, e.g. `<!-- ---` and `--- -->`. The comment is excluded from the
+    /// body, while comments without frontmatter are kept.
+    ///
+    /// ```
+    /// use markdown_frontmatter::Parser;
+    ///
+    /// let doc = "<!--\n---\ntitle: Hello\n---\n-->\nWorld";
+    ///
+    /// let (frontmatter, body) = Parser::new().html_comment(true).split(doc).unwrap();
+    /// assert_eq!(frontmatter.unwrap().as_str(), "title: Hello\n");
+    /// assert_eq!(body, "World");
+    /// ```
+    pub const fn html_comment(mut self, enabled: bool) -> Self {
+        self.html_comment = enabled;
+        self
+    }
+
+    /// Detects frontmatter in a leading fenced code block with the format
+    /// name as its info string, e.g. ```` ```yaml ```` or `~~~toml`.
+    ///
+    /// Names are case-insensitive and include custom formats, `yml` is an
+    /// alias of YAML.
+    ///
+    /// ```
+    /// use markdown_frontmatter::{FrontmatterFormat, Parser};
+    ///
+    /// let doc = "```yaml\ntitle: Hello\n```\nWorld";
+    ///
+    /// let (frontmatter, body) = Parser::new().fenced_code(true).split(doc).unwrap();
+    /// let frontmatter = frontmatter.unwrap();
+    /// assert_eq!(frontmatter.format(), FrontmatterFormat::Yaml);
+    /// assert_eq!(frontmatter.as_str(), "title: Hello\n");
+    /// assert_eq!(body, "World");
+    /// ```
+    pub const fn fenced_code(mut self, enabled: bool) -> Self {
+        self.fenced_code = enabled;
+        self
+    }
+
+    /// Detects format names following the opening delimiter, e.g. `---toml`
+    /// or `+++json`, enabled by default.
+    ///
+    /// The name selects the format, while the delimiter frames the
+    /// frontmatter. Names are looked up like in
+    /// [`fenced_code`](Self::fenced_code).
+    ///
+    /// ```
+    /// use markdown_frontmatter::{FrontmatterFormat, Parser};
+    ///
+    /// let doc = "---toml\ntitle = \"Hello\"\n---\nWorld";
+    ///
+    /// let (frontmatter, body) = Parser::new().split(doc).unwrap();
+    /// let frontmatter = frontmatter.unwrap();
+    /// assert_eq!(frontmatter.format(), FrontmatterFormat::Toml);
+    /// assert_eq!(frontmatter.as_str(), "title = \"Hello\"\n");
+    /// assert_eq!(body, "World");
+    ///
+    /// let (frontmatter, _) = Parser::new().format_tags(false).split(doc).unwrap();
+    /// assert!(frontmatter.is_none());
+    /// ```
+    pub const fn format_tags(mut self, enabled: bool) -> Self {
+        self.format_tags = enabled;
+        self
+    }
+
+    /// Fails with [`Error::AbsentFrontmatter`] on documents without
+    /// frontmatter instead of substituting an empty one.
+    pub const fn require_frontmatter(mut self, enabled: bool) -> Self {
+        self.require_frontmatter = enabled;
+        self
+    }
+
+    /// Restricts detection to the given formats, documents starting with
+    /// frontmatter of other formats are treated as documents without one.
+    ///
+    /// ```
+    /// use markdown_frontmatter::{FrontmatterFormat, Parser};
+    ///
+    /// let doc = "+++\ntitle = \"Hello\"\n+++\nWorld";
+    ///
+    /// let parser = Parser::new().allowed_formats([FrontmatterFormat::Yaml]);
+    /// let (frontmatter, body) = parser.split(doc).unwrap();
+    /// assert!(frontmatter.is_none());
+    /// assert_eq!(body, doc);
+    /// ```
+    pub fn allowed_formats(mut self, formats: impl IntoIterator<Item = FrontmatterFormat>) -> Self {
+        self.allowed_formats = Some(formats.into_iter().collect());
+        self
+    }
+
+    /// Skips whitespace before the opening delimiter, enabled by default.
+    ///
+    /// Disabled, frontmatter has to start on the first line, and documents
+    /// without frontmatter are returned as is.
+    pub const fn trim_leading_whitespace(mut self, enabled: bool) -> Self {
+        self.trim_leading_whitespace = enabled;
+        self
+    }
+
+    /// Sets the format of the empty frontmatter substituted for documents
+    /// without one, so the target type is deserialized by its parser.
+    ///
+    /// Defaults to the first enabled of JSON, TOML, YAML, RON and MMD.
+    #[cfg(feature = "_serde")]
+    pub const fn default_format(mut self, format: FrontmatterFormat) -> Self {
+        self.default_format = Some(format);
+        self
+    }
+
+    /// Splits a document into frontmatter and body like
+    /// [`split`](crate::split).
+    pub fn split<'a>(
+        &self,
+        content: &'a str,
+    ) -> Result<(Option<SplitFrontmatter<'a>>, &'a str), Error> {
+        let document = content;
+        let offset = if self.trim_leading_whitespace {
+            content.len() - content.trim_start().len()
+        } else {
+            0
+        };
+        let content = &content[offset..];
+        let mut lines = LineSpan::new(content);
+
+        let Some(Opening {
+            span: opening,
+            frame,
+            comment,
+        }) = self.open(&mut lines)
+        else {
+            // Empty document or no frontmatter
+            return self.without_frontmatter(content);
+        };
+        let format = frame.format;
+        let opening_span = Span::new(
+            document,
+            offset + opening.start..offset + opening.start + opening.line.len(),
+        );
+
+        let include_delimiters = frame.closing.is_none() && format.include_delimiters();
+        let matter_start = if include_delimiters {
+            opening.start
+        } else {
+            opening.next_start
+        };
+
+        while let Some(span) = lines.next() {
+            let Some((line, closes_comment)) = self.close(&frame, comment.is_some(), span.line)
+            else {
+                continue;
+            };
+            let matter_end = match (include_delimiters, closes_comment) {
+                (true, true) => span.start + line.len(),
+                (true, false) => span.next_start,
+                (false, _) => span.start,
+            };
+            let mut body_start = span.next_start;
+            if let Some(comment) = &comment
+                && !closes_comment
+            {
+                match lines.next() {
+                    Some(end) if end.line.trim() == "-->" => body_start = end.next_start,
+                    _ => {
+                        let opening = offset + comment.start..offset + comment.end;
+                        return Err(Error::AbsentClosingDelimiter(
+                            "HTML comment",
+                            Span::new(document, opening),
+                            "-->".into(),
+                        ));
+                    }
+                }
+            }
+            let matter = &content[matter_start..matter_end];
+            let frontmatter = SplitFrontmatter {
+                format,
+                matter,
+                start: offset + matter_start,
+                end: offset + matter_end,
+                opening: Some(opening_span),
+            };
+            return Ok((Some(frontmatter), &content[body_start..]));
+        }
+        if format == FrontmatterFormat::Mmd && frame.closing.is_none() && comment.is_none() {
+            // Metadata without a body
+            let frontmatter = SplitFrontmatter {
+                format,
+                matter: &content[matter_start..],
+                start: offset + matter_start,
+                end: offset + content.len(),
+                opening: Some(opening_span),
+            };
+            return Ok((Some(frontmatter), &content[content.len()..]));
+        }
+        let closing = frame.closing.unwrap_or(format.delimiter().1);
+        Err(Error::AbsentClosingDelimiter(
+            format.into(),
+            opening_span,
+            closing.into(),
+        ))
+    }
+
+    /// Parses frontmatter like [`parse`](crate::parse).
+    #[cfg(feature = "_serde")]
+    pub fn parse<'a, T: serde::de::DeserializeOwned>(
+        &self,
+        content: &'a str,
+    ) -> Result<(T, &'a str), Error> {
+        self.parse_borrowed(content)
+    }
+
+    /// Parses frontmatter like [`parse_borrowed`](crate::parse_borrowed).
+    #[cfg(feature = "_serde")]
+    pub fn parse_borrowed<'de, T: serde::Deserialize<'de>>(
+        &self,
+        content: &'de str,
+    ) -> Result<(T, &'de str), Error> {
+        let (maybe_frontmatter, body) = self.split(content)?;
+        let matter = maybe_frontmatter.unwrap_or_else(|| self.empty_frontmatter());
+        let frontmatter = matter.parse(content)?;
+        Ok((frontmatter, body))
+    }
+
+    /// Parses frontmatter from a reader like
+    /// [`parse_reader`](crate::parse_reader).
+    #[cfg(feature = "_serde")]
+    pub fn parse_reader<T: serde::de::DeserializeOwned, R: BufRead>(
+        &self,
+        mut reader: R,
+    ) -> Result<(T, BodyReader<R>), Error> {
+        let mut head = ReadHead::default();
+        if self.trim_leading_whitespace {
+            loop {
+                let n = whitespace_chunk(&mut head.head, reader.fill_buf().map_err(Error::Io)?);
+                if n == 0 {
+                    break;
+                }
+                reader.consume(n);
+            }
+        }
+        let chunk = reader.fill_buf().map_err(Error::Io)?;
+        let (frontmatter, read) = match self.read_opening(&mut head, chunk)? {
+            Some(done) => done,
+            None => {
+                let mut line = Vec::new();
+                loop {
+                    line.clear();
+                    loop {
+                        let (n, done) =
+                            line_chunk(&mut line, reader.fill_buf().map_err(Error::Io)?);
+                        reader.consume(n);
+                        if done {
+                            break;
+                        }
+                    }
+                    if let Some(done) = self.read_line(&mut head, &line)? {
+                        break done;
+                    }
+                }
+            }
+        };
+        Ok((frontmatter, io::Read::chain(io::Cursor::new(read), reader)))
+    }
+
+    /// Parses frontmatter from an async reader like
+    /// [`parse_async_reader`](crate::parse_async_reader).
+    #[cfg(all(feature = "async", feature = "_serde"))]
+    pub async fn parse_async_reader<T: serde::de::DeserializeOwned, R: AsyncBufRead + Unpin>(
+        &self,
+        mut reader: R,
+    ) -> Result<(T, crate::AsyncBodyReader<R>), Error> {
+        let mut head = ReadHead::default();
+        if self.trim_leading_whitespace {
+            loop {
+                let chunk = reader.fill_buf().await.map_err(Error::Io)?;
+                let n = whitespace_chunk(&mut head.head, chunk);
+                if n == 0 {
+                    break;
+                }
+                reader.consume_unpin(n);
+            }
+        }
+        let chunk = reader.fill_buf().await.map_err(Error::Io)?;
+        let (frontmatter, read) = match self.read_opening(&mut head, chunk)? {
+            Some(done) => done,
+            None => {
+                let mut line = Vec::new();
+                loop {
+                    line.clear();
+                    loop {
+                        let chunk = reader.fill_buf().await.map_err(Error::Io)?;
+                        let (n, done) = line_chunk(&mut line, chunk);
+                        reader.consume_unpin(n);
+                        if done {
+                            break;
+                        }
+                    }
+                    if let Some(done) = self.read_line(&mut head, &line)? {
+                        break done;
+                    }
+                }
+            }
+        };
+        let read = futures_util::io::Cursor::new(read);
+        Ok((frontmatter, futures_util::AsyncReadExt::chain(read, reader)))
+    }
+
+    /// Tells if frontmatter opens by the first chunk of a reader after leading
+    /// whitespace, peeking at the opening lines, so a document without
+    /// frontmatter stays unread. Opening lines that don't fit the chunk are
+    /// left to [`read_line`](Self::read_line).
+    #[cfg(feature = "_serde")]
+    fn read_opening<T: serde::de::DeserializeOwned>(
+        &self,
+        head: &mut ReadHead,
+        chunk: &[u8],
+    ) -> Result<Option<(T, Vec<u8>)>, Error> {
+        let opens = self
+            .opening_len(chunk)
+            .map(|n| self.opens(&chunk[..n]))
+            .transpose()?;
+        match opens {
+            Some(false) => Ok(Some((self.empty_frontmatter().parse("")?, Vec::new()))),
+            Some(true) => Ok(None),
+            None => {
+                head.opening = Some(Vec::new());
+                Ok(None)
+            }
+        }
+    }
+
+    /// Appends a line read from a reader, returning the frontmatter once it's
+    /// closed, along with the lines read past it, which start the body of a
+    /// document without frontmatter. An empty line means the end of the
+    /// reader.
+    #[cfg(feature = "_serde")]
+    fn read_line<T: serde::de::DeserializeOwned>(
+        &self,
+        head: &mut ReadHead,
+        line: &[u8],
+    ) -> Result<Option<(T, Vec<u8>)>, Error> {
+        let Some(opening) = &mut head.opening else {
+            return Ok(self
+                .push_line(&mut head.head, line)?
+                .map(|frontmatter| (frontmatter, Vec::new())));
+        };
+        opening.extend_from_slice(line);
+        if !line.is_empty() && self.opens_comment(opening) {
+            return Ok(None);
+        }
+        if !self.opens(opening)? {
+            let frontmatter = self.empty_frontmatter().parse("")?;
+            return Ok(Some((frontmatter, std::mem::take(opening))));
+        }
+        push_utf8(&mut head.head, opening)?;
+        head.opening = None;
+        Ok(None)
+    }
+
+    /// Returns the length of the lines at the start of a reader needed to
+    /// tell if frontmatter opens, or `None` if they don't end within a chunk.
+    #[cfg(feature = "_serde")]
+    fn opening_len(&self, bytes: &[u8]) -> Option<usize> {
+        let first = line_len(bytes)?;
+        if !self.opens_comment(&bytes[..first]) {
+            return Some(first);
+        }
+        line_len(&bytes[first..]).map(|second| first + second)
+    }
+
+    /// Returns `true` if the lines are a bare opening of an HTML comment, so
+    /// the opening line of frontmatter may follow.
+    #[cfg(feature = "_serde")]
+    fn opens_comment(&self, lines: &[u8]) -> bool {
+        self.html_comment
+            && lines
+                .strip_prefix(b"<!--")
+                .is_some_and(|rest| rest.trim_ascii().is_empty())
+    }
+
+    /// Returns `true` if frontmatter opens with the opening lines of a
+    /// reader.
+    #[cfg(feature = "_serde")]
+    fn opens(&self, buf: &[u8]) -> Result<bool, Error> {
+        let peeked = match std::str::from_utf8(buf) {
+            Ok(peeked) => peeked,
+            Err(e) => std::str::from_utf8(&buf[..e.valid_up_to()]).unwrap_or_default(),
+        };
+        match self.split(peeked) {
+            Ok((frontmatter, _)) => Ok(frontmatter.is_some()),
+            Err(Error::AbsentClosingDelimiter(..)) => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Appends a line read from a reader to the document read so far,
+    /// returning the frontmatter once it's closed. An empty line means the
+    /// end of the reader.
+    #[cfg(feature = "_serde")]
+    fn push_line<T: serde::de::DeserializeOwned>(
+        &self,
+        head: &mut String,
+        line: &[u8],
+    ) -> Result<Option<T>, Error> {
+        let line_start = head.len();
+        push_utf8(head, line)?;
+        let eof = line.is_empty();
+        // Only a closing line may end the frontmatter
+        if !eof && !self.may_close(head, line_start) {
+            return Ok(None);
+        }
+        match self.split(head) {
+            Ok((Some(frontmatter), _)) => frontmatter.parse(head).map(Some),
+            Ok((None, _)) if eof => self.empty_frontmatter().parse("").map(Some),
+            Err(Error::AbsentClosingDelimiter(..)) if !eof => Ok(None),
+            Err(e) => Err(e),
+            // The opening of an HTML comment
+            Ok((None, _)) => Ok(None),
+        }
+    }
+
+    /// Returns `true` if the last line of the document read so far, starting
+    /// at `line_start`, may close its frontmatter.
+    #[cfg(feature = "_serde")]
+    fn may_close(&self, head: &str, line_start: usize) -> bool {
+        let content = if self.trim_leading_whitespace {
+            head.trim_start()
+        } else {
+            head
+        };
+        let Some(opening) = self.open(&mut LineSpan::new(content)) else {
+            return true;
+        };
+        let line = head[line_start..].trim_end_matches(['\r', '\n']);
+        let comment = opening.comment.is_some();
+        comment && line.trim() == "-->" || self.close(&opening.frame, comment, line).is_some()
+    }
+
+    /// Frontmatter of a document without it.
+    #[cfg(feature = "_serde")]
+    fn empty_frontmatter(&self) -> SplitFrontmatter<'static> {
+        self.default_format
+            .map_or_else(SplitFrontmatter::default, SplitFrontmatter::empty)
+    }
+
+    /// Result of splitting a document without frontmatter.
+    fn without_frontmatter<'a>(
+        &self,
+        content: &'a str,
+    ) -> Result<(Option<SplitFrontmatter<'a>>, &'a str), Error> {
+        if self.require_frontmatter {
+            return Err(Error::AbsentFrontmatter);
+        }
+        Ok((None, content))
+    }
+
+    /// Returns `true` if detection of the format is allowed.
+    fn is_allowed(&self, format: FrontmatterFormat) -> bool {
+        self.allowed_formats
+            .as_ref()
+            .is_none_or(|formats| formats.contains(&format))
+    }
+
+    /// Finds the opening line of frontmatter, following the opening of an HTML
+    /// comment.
+    fn open<'a>(&self, lines: &mut impl Iterator<Item = LineSpan<'a>>) -> Option<Opening<'a>> {
+        let mut span = lines.next()?;
+        let mut comment = None;
+        if self.html_comment && span.line.starts_with("<!--") {
+            comment = Some(span.start..span.start + "<!--".len());
+            let inline = span.line["<!--".len()..].trim_start();
+            span = if inline.is_empty() {
+                lines.next()?
+            } else {
+                LineSpan {
+                    start: span.start + span.line.len() - inline.len(),
+                    next_start: span.next_start,
+                    line: inline.trim_end(),
+                }
+            };
+        }
+        let frame = self
+            .frame(span.line)
+            .filter(|frame| self.is_allowed(frame.format))?;
+        Some(Opening {
+            span,
+            frame,
+            comment,
+        })
+    }
+
+    /// Returns the closing line without the end of the comment, which may end
+    /// on it, and whether it does, if the line closes the frontmatter.
+    fn close<'l>(
+        &self,
+        frame: &Frame<'_>,
+        comment: bool,
+        line: &'l str,
+    ) -> Option<(&'l str, bool)> {
+        let (line, closes_comment) = match line.strip_suffix("-->") {
+            Some(line) if comment => (line.trim_end(), true),
+            _ => (line, false),
+        };
+        self.is_closing(frame, line)
+            .then_some((line, closes_comment))
+    }
+
+    /// Returns `true` if the line closes the frontmatter.
+    fn is_closing(&self, frame: &Frame<'_>, line: &str) -> bool {
+        match frame.closing {
+            Some(closing) => line.trim_end() == closing,
+            None => {
+                frame.format.is_closing(line)
+                    || self.pandoc_yaml && frame.format == FrontmatterFormat::Yaml && line == "..."
+            }
+        }
+    }
+
+    /// Detects the frontmatter framing by the opening line of a document.
+    fn frame<'a>(&self, line: &'a str) -> Option<Frame<'a>> {
+        if let Some(&(format, _, closing)) = self
+            .delimiters
+            .iter()
+            .find(|(_, opening, _)| line == *opening)
+        {
+            return Some(Frame {
+                format: self.configure(format),
+                closing: Some(closing),
+            });
+        }
+        if let Some(format) = self.detect(line) {
+            return Some(Frame {
+                format,
+                closing: None,
+            });
+        }
+        if self.fenced_code
+            && let Some(frame) = self.fence(line)
+        {
+            return Some(frame);
+        }
+        if self.format_tags {
+            return self.tagged(line);
+        }
+        None
+    }
+
+    /// Detects a format name following an opening delimiter which isn't a
+    /// part of the frontmatter, e.g. `---toml`.
+    fn tagged<'a>(&self, line: &'a str) -> Option<Frame<'a>> {
+        FrontmatterFormat::VARIANTS
+            .into_iter()
+            .filter(|format| !format.include_delimiters())
+            .find_map(|delimiters| {
+                let (opening, closing) = delimiters.delimiter();
+                let name = line.strip_prefix(opening)?.trim();
+                Some(Frame {
+                    format: self.format_by_name(name)?,
+                    closing: Some(closing),
+                })
+            })
+    }
+
+    /// Detects a fenced code block with a format name as its info string.
+    fn fence<'a>(&self, line: &'a str) -> Option<Frame<'a>> {
+        let fence_char = line.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+        let info = line.trim_start_matches(fence_char);
+        let fence = &line[..line.len() - info.len()];
+        if fence.len() < 3 {
+            return None;
+        }
+        let name = info.split_whitespace().next()?;
+        Some(Frame {
+            format: self.format_by_name(name)?,
+            closing: Some(fence),
+        })
+    }
+
+    /// Finds a format by its case-insensitive name.
+    fn format_by_name(&self, name: &str) -> Option<FrontmatterFormat> {
+        #[cfg(feature = "_serde")]
+        if let Some(&format) = self
+            .formats
+            .iter()
+            .find(|format| format.name().eq_ignore_ascii_case(name))
+        {
+            return Some(FrontmatterFormat::Custom(format));
+        }
+        let name = if name.eq_ignore_ascii_case("yml") {
+            "yaml"
+        } else {
+            name
+        };
+        FrontmatterFormat::VARIANTS
+            .into_iter()
+            .chain([FrontmatterFormat::Mmd])
+            .find(|&format| <&str>::from(format).eq_ignore_ascii_case(name))
+            .map(|format| self.configure(format))
+    }
+
+    /// Applies format options, e.g. parsing JSON as JSON5.
+    fn configure(&self, format: FrontmatterFormat) -> FrontmatterFormat {
+        #[cfg(feature = "json5")]
+        if self.json5 && format == FrontmatterFormat::Json {
+            return FrontmatterFormat::Json5;
+        }
+        format
+    }
+
+    /// Detects the format by the opening line of a document.
+    fn detect(&self, line: &str) -> Option<FrontmatterFormat> {
+        #[cfg(feature = "_serde")]
+        if let Some(&format) = self.formats.iter().find(|format| format.is_opening(line)) {
+            return Some(FrontmatterFormat::Custom(format));
+        }
+        let format = FrontmatterFormat::detect(line).map(|format| self.configure(format));
+        #[cfg(feature = "mmd")]
+        if self.mmd && format.is_none() && crate::mmd::is_metadata_line(line) {
+            return Some(FrontmatterFormat::Mmd);
+        }
+        format
+    }
+}
+
+/// Appends leading whitespace of a chunk read from a reader to a buffer,
+/// returning its length.
+#[cfg(feature = "_serde")]
+fn whitespace_chunk(buf: &mut String, bytes: &[u8]) -> usize {
+    let n = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    buf.extend(bytes[..n].iter().map(|&b| char::from(b)));
+    n
+}
+
+/// Appends a chunk read from a reader to a line up to its line break, which
+/// is found like by [`LineSpan`]. Returns the number of bytes to consume and
+/// whether the line is complete, an empty chunk means the end of the reader.
+#[cfg(feature = "_serde")]
+fn line_chunk(line: &mut Vec<u8>, bytes: &[u8]) -> (usize, bool) {
+    if line.last() == Some(&b'\r') {
+        // The `\r` ended the previous chunk
+        if bytes.first() == Some(&b'\n') {
+            line.push(b'\n');
+            return (1, true);
+        }
+        return (0, true);
+    }
+    let (n, done) = match line_break(bytes) {
+        Some((end, next)) => (next, bytes[end] == b'\n' || end + 1 < bytes.len()),
+        None => (bytes.len(), bytes.is_empty()),
+    };
+    line.extend_from_slice(&bytes[..n]);
+    (n, done)
+}
+
+/// Returns the length of the first line of a chunk read from a reader,
+/// including its line break, or `None` if the line doesn't end within the
+/// chunk.
+#[cfg(feature = "_serde")]
+fn line_len(bytes: &[u8]) -> Option<usize> {
+    let (end, next) = line_break(bytes)?;
+    // A `\r` may be followed by `\n` in the next chunk
+    (bytes[end] == b'\n' || end + 1 < bytes.len()).then_some(next)
+}
+
+/// Appends a line read from a reader to a buffer, failing if it isn't UTF-8.
+#[cfg(feature = "_serde")]
+fn push_utf8(buf: &mut String, line: &[u8]) -> Result<(), Error> {
+    let line = std::str::from_utf8(line)
+        .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+    buf.push_str(line);
+    Ok(())
+}
+
+/// A document read from a reader up to the end of its frontmatter.
+#[cfg(feature = "_serde")]
+#[derive(Default)]
+struct ReadHead {
+    /// The document read so far, starting with leading whitespace.
+    head: String,
+    /// Opening lines that didn't fit the buffer of the reader, read to tell
+    /// if frontmatter opens, `None` once it does.
+    opening: Option<Vec<u8>>,
+}
+
+/// Opening line of frontmatter.
+struct Opening<'a> {
+    span: LineSpan<'a>,
+    frame: Frame<'a>,
+    /// Range of the `<!--` wrapping the frontmatter.
+    comment: Option<Range<usize>>,
+}
+
+/// Frontmatter framing detected by the opening line.
+struct Frame<'a> {
+    format: FrontmatterFormat,
+    /// Closing line replacing the closing delimiter of the format, the
+    /// delimiters aren't a part of the frontmatter then.
+    closing: Option<&'a str>,
+}
+
+#[cfg(all(test, feature = "_serde"))]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{BoxError, Value};
+
+    /// `key = value` lines wrapped in `[[[`/`]]]`, delimiters included.
+    #[derive(Debug)]
+    struct KeyValue;
+
+    impl Format for KeyValue {
+        fn name(&self) -> &'static str {
+            "key-value"
+        }
+
+        fn delimiters(&self) -> (&'static str, &'static str) {
+            ("[[[", "]]]")
+        }
+
+        fn include_delimiters(&self) -> bool {
+            true
+        }
+
+        fn parse(&self, matter: &str) -> Result<Value, BoxError> {
+            let mut lines = matter.lines();
+            lines.next();
+            lines.next_back();
+            lines
+                .map(|line| {
+                    let (key, value) = line.split_once(" = ").ok_or("expected `key = value`")?;
+                    let value = value
+                        .parse()
+                        .map_or_else(|_| Value::String(value.into()), Value::Integer);
+                    Ok((key.into(), value))
+                })
+                .collect::<Result<_, BoxError>>()
+                .map(Value::Table)
+        }
+    }
+
+    /// Takes over the YAML delimiter.
+    #[derive(Debug)]
+    struct Dashes;
+
+    impl Format for Dashes {
+        fn name(&self) -> &'static str {
+            "dashes"
+        }
+
+        fn delimiters(&self) -> (&'static str, &'static str) {
+            ("---", "---")
+        }
+
+        fn is_closing(&self, line: &str) -> bool {
+            line.starts_with("---")
+        }
+
+        fn parse(&self, matter: &str) -> Result<Value, BoxError> {
+            Ok(Value::String(matter.trim().into()))
+        }
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Frontmatter {
+        title: String,
+        weight: Option<i64>,
+    }
+
+    #[test]
+    fn custom_format() {
+        let doc = "\n[[[\ntitle = Hello\nweight = 1\n]]]\nWorld";
+        let parser = Parser::new().format(&KeyValue);
+
+        let (frontmatter, body) = parser.split(doc).unwrap();
+        let frontmatter = frontmatter.unwrap();
+        assert_eq!(frontmatter.format(), FrontmatterFormat::Custom(&KeyValue));
+        assert_eq!(<&str>::from(frontmatter.format()), "key-value");
+        assert_eq!(
+            frontmatter.as_str(),
+            "[[[\ntitle = Hello\nweight = 1\n]]]\n"
+        );
+        assert_eq!(&doc[frontmatter.span()], frontmatter.as_str());
+        assert_eq!(body, "World");
+
+        let (frontmatter, body) = parser.parse::<Frontmatter>(doc).unwrap();
+        assert_eq!(
+            frontmatter,
+            Frontmatter {
+                title: "Hello".into(),
+                weight: Some(1),
+            }
+        );
+        assert_eq!(body, "World");
+    }
+
+    #[test]
+    fn custom_format_is_not_detected_by_default() {
+        let doc = "[[[\ntitle = Hello\n]]]\nWorld";
+        assert!(crate::split(doc).unwrap().0.is_none());
+    }
+
+    #[test]
+    fn custom_format_takes_precedence() {
+        let doc = "---\nfoo: bar\n-----\nWorld";
+        let (frontmatter, body) = Parser::new().format(&Dashes).split(doc).unwrap();
+        assert_eq!(
+            frontmatter.unwrap().format(),
+            FrontmatterFormat::Custom(&Dashes)
+        );
+        assert_eq!(frontmatter.unwrap().as_str(), "foo: bar\n");
+        assert_eq!(body, "World");
+    }
+
+    #[test]
+    fn custom_format_unclosed() {
+        let err = Parser::new()
+            .format(&KeyValue)
+            .split("[[[\ntitle = Hello\n")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::AbsentClosingDelimiter("key-value", ..)
+        ));
+    }
+
+    #[test]
+    fn custom_format_invalid() {
+        let doc = "[[[\ntitle: Hello\n]]]\n";
+        let err = Parser::new()
+            .format(&KeyValue)
+            .parse::<Frontmatter>(doc)
+            .unwrap_err();
+        assert!(matches!(err, Error::Invalid("key-value", ..)));
+        assert_eq!(
+            err.to_string(),
+            "invalid key-value syntax at line 1, column 1"
+        );
+    }
+
+    #[test]
+    fn custom_format_deserialize() {
+        let doc = "[[[\ntitle = Hello\nweight = heavy\n]]]\n";
+        let err = Parser::new()
+            .format(&KeyValue)
+            .parse::<Frontmatter>(doc)
+            .unwrap_err();
+        assert!(matches!(err, Error::Deserialize("key-value", ..)));
+        assert_eq!(err.path(), Some("weight"));
+    }
+
+    #[test]
+    fn custom_format_equality() {
+        assert_eq!(
+            FrontmatterFormat::Custom(&KeyValue),
+            FrontmatterFormat::Custom(&KeyValue)
+        );
+        assert_ne!(
+            FrontmatterFormat::Custom(&KeyValue),
+            FrontmatterFormat::Custom(&Dashes)
+        );
+        assert_ne!(FrontmatterFormat::Custom(&Dashes), FrontmatterFormat::Yaml);
+    }
+
+    #[test]
+    fn pandoc_yaml() {
+        let doc = "---\ntitle: Hello\n...\nWorld";
+        assert!(matches!(
+            crate::split(doc).unwrap_err(),
+            Error::AbsentClosingDelimiter("YAML", ..)
+        ));
+
+        let parser = Parser::new().pandoc_yaml(true);
+        let (frontmatter, body) = parser.split(doc).unwrap();
+        assert_eq!(frontmatter.unwrap().as_str(), "title: Hello\n");
+        assert_eq!(body, "World");
+
+        let doc = "---\ntitle: Hello\n---\nWorld";
+        let (frontmatter, _) = parser.split(doc).unwrap();
+        assert_eq!(frontmatter.unwrap().as_str(), "title: Hello\n");
+    }
+
+    #[test]
+    fn pandoc_yaml_ignores_other_formats() {
+        let doc = "+++\ntitle = 1\n...\n+++\nWorld";
+        let (frontmatter, _) = Parser::new().pandoc_yaml(true).split(doc).unwrap();
+        assert_eq!(frontmatter.unwrap().as_str(), "title = 1\n...\n");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn pandoc_yaml_parse() {
+        let doc = "---\ntitle: Hello\n...\nWorld";
+        let (frontmatter, body) = Parser::new()
+            .pandoc_yaml(true)
+            .parse::<Frontmatter>(doc)
+            .unwrap();
+        assert_eq!(frontmatter.title, "Hello");
+        assert_eq!(body, "World");
+    }
+
+    mod fenced_code {
+        use super::*;
+
+        fn split(doc: &str) -> Result<(Option<SplitFrontmatter<'_>>, &str), Error> {
+            Parser::new().fenced_code(true).split(doc)
+        }
+
+        #[test]
+        fn yaml() {
+            let doc = "\n```yaml\ntitle: Hello\n```\nWorld";
+            let (frontmatter, body) = split(doc).unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.format(), FrontmatterFormat::Yaml);
+            assert_eq!(frontmatter.as_str(), "title: Hello\n");
+            assert_eq!(&doc[frontmatter.span()], frontmatter.as_str());
+            assert_eq!(body, "World");
+        }
+
+        #[test]
+        fn tildes_and_info_string() {
+            let doc = "~~~~TOML title=\"meta\"\ntitle = \"Hello\"\n~~~\n~~~~\nWorld";
+            let (frontmatter, body) = split(doc).unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.format(), FrontmatterFormat::Toml);
+            assert_eq!(frontmatter.as_str(), "title = \"Hello\"\n~~~\n");
+            assert_eq!(body, "World");
+        }
+
+        #[test]
+        fn delimiters_excluded() {
+            let doc = "```json\n{\n  \"title\": \"Hello\"\n}\n```\nWorld";
+            let (frontmatter, _) = split(doc).unwrap();
+            assert_eq!(
+                frontmatter.unwrap().as_str(),
+                "{\n  \"title\": \"Hello\"\n}\n"
+            );
+        }
+
+        #[test]
+        fn custom_format_name() {
+            let doc = "```Dashes\nfoo\n```\nWorld";
+            let (frontmatter, _) = Parser::new()
+                .fenced_code(true)
+                .format(&Dashes)
+                .split(doc)
+                .unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.format(), FrontmatterFormat::Custom(&Dashes));
+            assert_eq!(frontmatter.as_str(), "foo\n");
+        }
+
+        #[test]
+        fn not_frontmatter() {
+            for doc in [
+                "```rust\nfn main() {}\n```\n",
+                "```\nfoo\n```\n",
+                "``yaml\n``\n",
+            ] {
+                let (frontmatter, body) = split(doc).unwrap();
+                assert!(frontmatter.is_none(), "{doc}");
+                assert_eq!(body, doc);
+            }
+        }
+
+        #[test]
+        fn disabled_by_default() {
+            let doc = "```yaml\ntitle: Hello\n```\nWorld";
+            assert!(crate::split(doc).unwrap().0.is_none());
+        }
+
+        #[test]
+        fn unclosed() {
+            let err = split("```yml\ntitle: Hello\n").unwrap_err();
+            assert!(matches!(err, Error::AbsentClosingDelimiter("YAML", ..)));
+        }
+
+        #[cfg(feature = "json")]
+        #[test]
+        fn parse() {
+            let doc = "```json\n{\n  \"title\": \"Hello\"\n}\n```\nWorld";
+            let (frontmatter, body) = Parser::new()
+                .fenced_code(true)
+                .parse::<Frontmatter>(doc)
+                .unwrap();
+            assert_eq!(frontmatter.title, "Hello");
+            assert_eq!(body, "World");
+        }
+    }
+
+    mod strictness {
+        use super::*;
+
+        const WITHOUT_FRONTMATTER: &str = "\n  hello world";
+
+        #[test]
+        fn require_frontmatter() {
+            let parser = Parser::new().require_frontmatter(true);
+            for doc in ["", WITHOUT_FRONTMATTER, "<!--\nnote\n-->\n"] {
+                assert!(matches!(parser.split(doc), Err(Error::AbsentFrontmatter)));
+            }
+            assert!(matches!(
+                parser.parse::<Frontmatter>(WITHOUT_FRONTMATTER),
+                Err(Error::AbsentFrontmatter)
+            ));
+            assert!(parser.split("---\n---\n").unwrap().0.is_some());
+        }
+
+        #[test]
+        fn allowed_formats() {
+            let parser = Parser::new().allowed_formats([FrontmatterFormat::Yaml]);
+            for doc in ["+++\n+++\nWorld", "---toml\n---\nWorld"] {
+                let (frontmatter, body) = parser.split(doc).unwrap();
+                assert!(frontmatter.is_none(), "{doc}");
+                assert_eq!(body, doc);
+            }
+            let (frontmatter, _) = parser.split("---\n---\nWorld").unwrap();
+            assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Yaml);
+
+            let err = parser
+                .require_frontmatter(true)
+                .split("+++\n+++\nWorld")
+                .unwrap_err();
+            assert!(matches!(err, Error::AbsentFrontmatter));
+        }
+
+        #[test]
+        fn keep_leading_whitespace() {
+            let parser = Parser::new().trim_leading_whitespace(false);
+            let (frontmatter, body) = parser.split(WITHOUT_FRONTMATTER).unwrap();
+            assert!(frontmatter.is_none());
+            assert_eq!(body, WITHOUT_FRONTMATTER);
+
+            let doc = "\n---\ntitle: Hello\n---\nWorld";
+            let (frontmatter, body) = parser.split(doc).unwrap();
+            assert!(frontmatter.is_none());
+            assert_eq!(body, doc);
+
+            let (frontmatter, body) = parser.split(&doc[1..]).unwrap();
+            assert_eq!(frontmatter.unwrap().as_str(), "title: Hello\n");
+            assert_eq!(body, "World");
+        }
+
+        #[cfg(feature = "toml")]
+        #[test]
+        fn default_format() {
+            #[derive(Debug, Deserialize)]
+            #[expect(dead_code)]
+            struct Required {
+                title: String,
+            }
+
+            let err = Parser::new()
+                .default_format(FrontmatterFormat::Toml)
+                .parse::<Required>(WITHOUT_FRONTMATTER)
+                .unwrap_err();
+            assert!(matches!(err, Error::DeserializeToml(..)));
+
+            let (frontmatter, body) = Parser::new()
+                .default_format(FrontmatterFormat::Toml)
+                .parse::<Value>(WITHOUT_FRONTMATTER)
+                .unwrap();
+            assert_eq!(frontmatter, Value::Table(Default::default()));
+            assert_eq!(body, WITHOUT_FRONTMATTER.trim_start());
+        }
+    }
+
+    mod delimiters {
+        use super::*;
+
+        #[test]
+        fn alternative_delimiters() {
+            let parser = Parser::new()
+                .delimiters(FrontmatterFormat::Json, ";;;", ";;;")
+                .delimiters(FrontmatterFormat::Yaml, "~~~", "~~~");
+
+            let doc = "\n;;;\n{\"title\": \"Hello\"}\n;;;\nWorld";
+            let (frontmatter, body) = parser.split(doc).unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.format(), FrontmatterFormat::Json);
+            assert_eq!(frontmatter.as_str(), "{\"title\": \"Hello\"}\n");
+            assert_eq!(&doc[frontmatter.span()], frontmatter.as_str());
+            assert_eq!(body, "World");
+
+            let doc = "~~~\ntitle: Hello\n~~~\nWorld";
+            let (frontmatter, _) = parser.split(doc).unwrap();
+            assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Yaml);
+
+            let doc = "---\ntitle: Hello\n---\nWorld";
+            let (frontmatter, _) = parser.split(doc).unwrap();
+            assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Yaml);
+        }
+
+        #[test]
+        fn different_closing() {
+            let doc = "<<<\ntitle = 1\n>>>\nWorld";
+            let (frontmatter, body) = Parser::new()
+                .delimiters(FrontmatterFormat::Toml, "<<<", ">>>")
+                .split(doc)
+                .unwrap();
+            assert_eq!(frontmatter.unwrap().as_str(), "title = 1\n");
+            assert_eq!(body, "World");
+        }
+
+        #[test]
+        fn take_precedence() {
+            let doc = "---\ntitle = 1\n---\nWorld";
+            let (frontmatter, _) = Parser::new()
+                .delimiters(FrontmatterFormat::Toml, "---", "---")
+                .split(doc)
+                .unwrap();
+            assert_eq!(frontmatter.unwrap().format(), FrontmatterFormat::Toml);
+        }
+
+        #[test]
+        fn unclosed() {
+            let err = Parser::new()
+                .delimiters(FrontmatterFormat::Json, ";;;", ";;;")
+                .split(";;;\n{}\n")
+                .unwrap_err();
+            assert!(matches!(err, Error::AbsentClosingDelimiter("JSON", ..)));
+        }
+
+        #[cfg(feature = "json")]
+        #[test]
+        fn parse() {
+            let doc = ";;;\n{\"title\": \"Hello\"}\n;;;\nWorld";
+            let (frontmatter, body) = Parser::new()
+                .delimiters(FrontmatterFormat::Json, ";;;", ";;;")
+                .parse::<Frontmatter>(doc)
+                .unwrap();
+            assert_eq!(frontmatter.title, "Hello");
+            assert_eq!(body, "World");
+        }
+    }
+
+    mod format_tags {
+        use super::*;
+
+        #[test]
+        fn dashes() {
+            let doc = "---toml\ntitle = \"Hello\"\n---\nWorld";
+            let (frontmatter, body) = crate::split(doc).unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.format(), FrontmatterFormat::Toml);
+            assert_eq!(frontmatter.as_str(), "title = \"Hello\"\n");
+            assert_eq!(&doc[frontmatter.span()], frontmatter.as_str());
+            assert_eq!(body, "World");
+        }
+
+        #[test]
+        fn pluses() {
+            let doc = "+++ YAML\ntitle: Hello\n+++\nWorld";
+            let (frontmatter, body) = crate::split(doc).unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.format(), FrontmatterFormat::Yaml);
+            assert_eq!(frontmatter.as_str(), "title: Hello\n");
+            assert_eq!(body, "World");
+        }
+
+        #[test]
+        fn delimiters_excluded() {
+            let doc = "---json\n{\"title\": \"Hello\"}\n---\nWorld";
+            let (frontmatter, _) = crate::split(doc).unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.format(), FrontmatterFormat::Json);
+            assert_eq!(frontmatter.as_str(), "{\"title\": \"Hello\"}\n");
+        }
+
+        #[test]
+        fn unknown_name() {
+            let doc = "---markdown\n---\nWorld";
+            let (frontmatter, body) = crate::split(doc).unwrap();
+            assert!(frontmatter.is_none());
+            assert_eq!(body, doc);
+        }
+
+        #[test]
+        fn disabled() {
+            let doc = "---toml\ntitle = \"Hello\"\n---\nWorld";
+            let (frontmatter, _) = Parser::new().format_tags(false).split(doc).unwrap();
+            assert!(frontmatter.is_none());
+        }
+
+        #[test]
+        fn unclosed() {
+            let err = crate::split("---toml\ntitle = 1\n+++\n").unwrap_err();
+            assert!(matches!(err, Error::AbsentClosingDelimiter("TOML", ..)));
+        }
+
+        #[cfg(feature = "json")]
+        #[test]
+        fn parse() {
+            let doc = "---json\n{\"title\": \"Hello\"}\n---\nWorld";
+            let (frontmatter, body) = crate::parse::<Frontmatter>(doc).unwrap();
+            assert_eq!(frontmatter.title, "Hello");
+            assert_eq!(body, "World");
+        }
+    }
+
+    mod html_comment {
+        use super::*;
+
+        fn split(doc: &str) -> Result<(Option<SplitFrontmatter<'_>>, &str), Error> {
+            Parser::new().html_comment(true).split(doc)
+        }
+
+        #[test]
+        fn markers_on_own_lines() {
+            let doc = "\n<!--\n---\ntitle: Hello\n---\n-->\nWorld";
+            let (frontmatter, body) = split(doc).unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.format(), FrontmatterFormat::Yaml);
+            assert_eq!(frontmatter.as_str(), "title: Hello\n");
+            assert_eq!(&doc[frontmatter.span()], frontmatter.as_str());
+            assert_eq!(body, "World");
+        }
+
+        #[test]
+        fn markers_on_delimiter_lines() {
+            let doc = "<!-- +++\ntitle = \"Hello\"\n+++ -->\nWorld";
+            let (frontmatter, body) = split(doc).unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.format(), FrontmatterFormat::Toml);
+            assert_eq!(frontmatter.as_str(), "title = \"Hello\"\n");
+            assert_eq!(body, "World");
+        }
+
+        #[test]
+        fn delimiters_included() {
+            let doc = "<!-- {\n  \"title\": \"Hello\"\n} -->\nWorld";
+            let (frontmatter, body) = split(doc).unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.as_str(), "{\n  \"title\": \"Hello\"\n}");
+            assert_eq!(&doc[frontmatter.span()], frontmatter.as_str());
+            assert_eq!(body, "World");
+        }
+
+        #[test]
+        fn comment_without_frontmatter() {
+            for doc in ["<!-- TOC -->\n# Title", "<!--\nnote\n-->\nWorld", "<!--"] {
+                let (frontmatter, body) = split(doc).unwrap();
+                assert!(frontmatter.is_none(), "{doc}");
+                assert_eq!(body, doc);
+            }
+        }
+
+        #[test]
+        fn disabled_by_default() {
+            let doc = "<!--\n---\ntitle: Hello\n---\n-->\nWorld";
+            assert!(crate::split(doc).unwrap().0.is_none());
+        }
+
+        #[test]
+        fn unclosed_comment() {
+            let doc = "<!--\n---\ntitle: Hello\n---\nWorld";
+            let err = split(doc).unwrap_err();
+            assert!(matches!(
+                err,
+                Error::AbsentClosingDelimiter("HTML comment", span, _) if span.range() == (0..4)
+            ));
+        }
+
+        #[test]
+        fn unclosed_frontmatter() {
+            let doc = "<!--\n---\ntitle: Hello\n-->\nWorld";
+            let err = split(doc).unwrap_err();
+            assert!(matches!(err, Error::AbsentClosingDelimiter("YAML", ..)));
+        }
+
+        #[cfg(feature = "yaml")]
+        #[test]
+        fn parse() {
+            let doc = "<!--\n---\ntitle: Hello\n---\n-->\nWorld";
+            let (frontmatter, body) = Parser::new()
+                .html_comment(true)
+                .parse::<Frontmatter>(doc)
+                .unwrap();
+            assert_eq!(frontmatter.title, "Hello");
+            assert_eq!(body, "World");
+        }
+    }
+
+    #[cfg(feature = "mmd")]
+    mod mmd {
+        use super::*;
+
+        #[test]
+        fn opt_in() {
+            let doc = "Title: Hello\n\nWorld";
+            assert!(Parser::new().split(doc).unwrap().0.is_none());
+
+            let (frontmatter, body) = Parser::new().mmd(true).split(doc).unwrap();
+            let frontmatter = frontmatter.unwrap();
+            assert_eq!(frontmatter.format(), FrontmatterFormat::Mmd);
+            assert_eq!(frontmatter.as_str(), "Title: Hello\n\n");
+            assert_eq!(body, "World");
+        }
+
+        #[test]
+        fn without_body() {
+            let doc = "\nTitle: Hello\nWeight: 1";
+            let (frontmatter, body) = Parser::new().mmd(true).split(doc).unwrap();
+            assert_eq!(frontmatter.unwrap().as_str(), "Title: Hello\nWeight: 1");
+            assert_eq!(&doc[frontmatter.unwrap().span()], "Title: Hello\nWeight: 1");
+            assert_eq!(body, "");
+        }
+
+        #[test]
+        fn not_metadata() {
+            let parser = Parser::new().mmd(true);
+            for doc in ["# Title: Hello\n", "https://example.com\n", "Hello\n"] {
+                assert!(parser.split(doc).unwrap().0.is_none(), "{doc}");
+            }
+        }
+
+        #[test]
+        fn parse() {
+            let doc = "Title: Hello\nWeight: 1\n\nWorld";
+            let (frontmatter, body) = Parser::new().mmd(true).parse::<Value>(doc).unwrap();
+            assert_eq!(frontmatter.get("title").unwrap().as_str(), Some("Hello"));
+            assert_eq!(frontmatter.get("weight").unwrap().as_str(), Some("1"));
+            assert_eq!(body, "World");
+        }
+
+        #[test]
+        fn invalid() {
+            let doc = "Title: Hello\nnot metadata\n\nWorld";
+            let err = Parser::new().mmd(true).parse::<Value>(doc).unwrap_err();
+            assert!(matches!(err, Error::Invalid("MMD", ..)));
+            assert_eq!(err.span().unwrap().line, 2);
+        }
+
+        #[test]
+        fn deserialize() {
+            let doc = "Title: Hello\nWeight: heavy\n\nWorld";
+            let err = Parser::new()
+                .mmd(true)
+                .parse::<Frontmatter>(doc)
+                .unwrap_err();
+            assert!(matches!(err, Error::Deserialize("MMD", ..)));
+            assert_eq!(err.path(), Some("weight"));
+        }
+
+        #[cfg(any(feature = "json", feature = "toml", feature = "yaml", feature = "ron"))]
+        #[test]
+        fn not_serializable() {
+            let err = crate::to_string(&(), FrontmatterFormat::Mmd, "").unwrap_err();
+            assert!(matches!(err, Error::NotSerializable("MMD")));
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    mod reader {
+        use std::io::{BufReader, Read};
+
+        use super::*;
+        use crate::Table;
+
+        /// Parses a document read in chunks of a given size, returning the
+        /// frontmatter and the rest of the reader.
+        fn parse_reader<T: serde::de::DeserializeOwned>(
+            parser: &Parser,
+            doc: &[u8],
+            chunk: usize,
+        ) -> Result<(T, String), Error> {
+            let reader = BufReader::with_capacity(chunk, doc);
+            let (frontmatter, mut body) = parser.parse_reader(reader)?;
+            let mut rest = String::new();
+            body.read_to_string(&mut rest).unwrap();
+            Ok((frontmatter, rest))
+        }
+
+        #[test]
+        fn stops_at_body() {
+            let doc = "\n---\ntitle: Hello\n---\nWorld\n---\n";
+            for chunk in [1, 2, 3, 4, 8 * 1024] {
+                let (frontmatter, rest) =
+                    parse_reader::<Frontmatter>(&Parser::new(), doc.as_bytes(), chunk).unwrap();
+                assert_eq!(frontmatter.title, "Hello");
+                assert_eq!(rest, "World\n---\n", "{chunk}");
+            }
+        }
+
+        #[test]
+        fn line_breaks_across_chunks() {
+            let doc = "---\r\ntitle: Hello\r---\r\nWorld";
+            for chunk in 4..8 {
+                let (frontmatter, rest) =
+                    parse_reader::<Frontmatter>(&Parser::new(), doc.as_bytes(), chunk).unwrap();
+                assert_eq!(frontmatter.title, "Hello");
+                assert_eq!(rest, "World", "{chunk}");
+            }
+        }
+
+        #[test]
+        fn without_frontmatter() {
+            let doc = "\n  hello world\n---\n";
+            let (frontmatter, rest) =
+                parse_reader::<Value>(&Parser::new(), doc.as_bytes(), 64).unwrap();
+            assert_eq!(frontmatter, Value::Table(Table::default()));
+            assert_eq!(rest, "hello world\n---\n");
+
+            // The first line doesn't fit the buffer, so it's read
+            for chunk in [1, 2, 3] {
+                let (frontmatter, rest) =
+                    parse_reader::<Value>(&Parser::new(), doc.as_bytes(), chunk).unwrap();
+                assert_eq!(frontmatter, Value::Table(Table::default()));
+                assert_eq!(rest, "hello world\n---\n", "{chunk}");
+            }
+
+            let doc = format!("{}\nrest\n", "x".repeat(10 * 1024));
+            let (_, rest) =
+                parse_reader::<Value>(&Parser::new(), doc.as_bytes(), 8 * 1024).unwrap();
+            assert_eq!(rest, doc);
+
+            let parser = Parser::new().require_frontmatter(true);
+            let err = parse_reader::<Value>(&parser, doc.as_bytes(), 64).unwrap_err();
+            assert!(matches!(err, Error::AbsentFrontmatter));
+        }
+
+        #[test]
+        fn html_comment() {
+            let parser = Parser::new().html_comment(true);
+            for doc in [
+                "<!--\n---\ntitle: Hello\n---\n-->\nWorld",
+                "<!-- ---\ntitle: Hello\n--- -->\nWorld",
+            ] {
+                for chunk in [1, 2, 3, 6, 64] {
+                    let (frontmatter, rest) =
+                        parse_reader::<Frontmatter>(&parser, doc.as_bytes(), chunk).unwrap();
+                    assert_eq!(frontmatter.title, "Hello");
+                    assert_eq!(rest, "World", "{doc} {chunk}");
+                }
+            }
+
+            let doc = "<!--\nhello world\n-->\n";
+            for chunk in [1, 2, 3, 6, 64] {
+                let (frontmatter, rest) =
+                    parse_reader::<Value>(&parser, doc.as_bytes(), chunk).unwrap();
+                assert_eq!(frontmatter, Value::Table(Table::default()));
+                assert_eq!(rest, doc, "{chunk}");
+            }
+        }
+
+        #[cfg(feature = "mmd")]
+        #[test]
+        fn mmd() {
+            let doc = "Title: Hello\nWeight: 1\n\nWorld";
+            let parser = Parser::new().mmd(true);
+            let (frontmatter, rest) = parse_reader::<Value>(&parser, doc.as_bytes(), 64).unwrap();
+            assert_eq!(
+                frontmatter.get("title"),
+                Some(&Value::String("Hello".into()))
+            );
+            assert_eq!(rest, "World");
+        }
+
+        #[test]
+        fn errors_are_located_in_document() {
+            let doc = "\n---\ntitle: Hello\n";
+            let err = parse_reader::<Frontmatter>(&Parser::new(), doc.as_bytes(), 4).unwrap_err();
+            let Error::AbsentClosingDelimiter("YAML", span, _) = err else {
+                panic!("{err:?}");
+            };
+            assert_eq!(span.range(), 1..4);
+
+            let doc = "---\ntitle: [\n---\nWorld";
+            let err = parse_reader::<Frontmatter>(&Parser::new(), doc.as_bytes(), 64).unwrap_err();
+            assert!(matches!(err, Error::InvalidYaml(..)), "{err:?}");
+        }
+
+        #[test]
+        fn invalid_utf8() {
+            let doc = b"---\ntitle: \xff\n---\n";
+            let err = parse_reader::<Frontmatter>(&Parser::new(), doc, 64).unwrap_err();
+            assert!(
+                matches!(&err, Error::Io(e) if e.kind() == io::ErrorKind::InvalidData),
+                "{err:?}"
+            );
+        }
+
+        #[cfg(feature = "async")]
+        mod async_reader {
+            use futures_util::{AsyncReadExt, io};
+
+            use super::*;
+
+            /// Async counterpart of [`parse_reader`](super::parse_reader).
+            fn parse_async_reader<T: serde::de::DeserializeOwned>(
+                parser: &Parser,
+                doc: &[u8],
+                chunk: usize,
+            ) -> Result<(T, String), Error> {
+                futures_executor::block_on(async {
+                    let reader = io::BufReader::with_capacity(chunk, io::Cursor::new(doc));
+                    let (frontmatter, mut body) = parser.parse_async_reader(reader).await?;
+                    let mut rest = String::new();
+                    body.read_to_string(&mut rest).await.unwrap();
+                    Ok((frontmatter, rest))
+                })
+            }
+
+            #[test]
+            fn stops_at_body() {
+                let doc = "\n---\r\ntitle: Hello\r---\r\nWorld\n---\n";
+                for chunk in [1, 2, 3, 4, 5, 6, 8 * 1024] {
+                    let (frontmatter, rest) =
+                        parse_async_reader::<Frontmatter>(&Parser::new(), doc.as_bytes(), chunk)
+                            .unwrap();
+                    assert_eq!(frontmatter.title, "Hello");
+                    assert_eq!(rest, "World\n---\n", "{chunk}");
+                }
+            }
+
+            #[test]
+            fn without_frontmatter() {
+                let doc = "\n  hello world\n---\n";
+                for chunk in [1, 2, 3, 64] {
+                    let (frontmatter, rest) =
+                        parse_async_reader::<Value>(&Parser::new(), doc.as_bytes(), chunk).unwrap();
+                    assert_eq!(frontmatter, Value::Table(Table::default()));
+                    assert_eq!(rest, "hello world\n---\n", "{chunk}");
+                }
+            }
+
+            #[test]
+            fn absent_closing_delimiter() {
+                let doc = "\n---\ntitle: Hello\n";
+                let err = parse_async_reader::<Frontmatter>(&Parser::new(), doc.as_bytes(), 4)
+                    .unwrap_err();
+                let Error::AbsentClosingDelimiter("YAML", span, _) = err else {
+                    panic!("{err:?}");
+                };
+                assert_eq!(span.range(), 1..4);
+            }
+        }
+    }
+
+    #[cfg(any(feature = "json", feature = "toml", feature = "yaml", feature = "ron"))]
+    #[test]
+    fn custom_format_is_not_serializable() {
+        let err = crate::to_string(&(), FrontmatterFormat::Custom(&KeyValue), "").unwrap_err();
+        assert!(matches!(err, Error::NotSerializable("key-value")));
+    }
+}
@@ -0,0 +1,717 @@
+use std::fmt;
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{
+        self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess,
+        Visitor,
+        value::{MapDeserializer, SeqDeserializer},
+    },
+    forward_to_deserialize_any,
+    ser::SerializeMap,
+};
+
+/// The field name `toml` uses to pass datetimes through serde.
+const TOML_DATETIME_FIELD: &str = "$__toml_private_datetime";
+
+#[cfg(feature = "toml")]
+thread_local! {
+    /// Whether datetimes are serialized as TOML datetimes rather than strings.
+    static TOML_DATETIMES: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Serializes datetimes of values as TOML datetimes within a closure, the
+/// other serializers get them as strings.
+#[cfg(feature = "toml")]
+pub(crate) fn with_toml_datetimes<R>(f: impl FnOnce() -> R) -> R {
+    let enclosing = TOML_DATETIMES.replace(true);
+    let result = f();
+    TOML_DATETIMES.set(enclosing);
+    result
+}
+
+/// A format-agnostic frontmatter value.
+///
+/// Any of the supported formats can be parsed into it, keeping TOML datetimes
+/// and YAML tags, so the code working with it doesn't depend on the format
+/// chosen by the author of a document.
+///
+/// ```
+/// use markdown_frontmatter::Value;
+///
+/// let doc = "+++\ndate = 1979-05-27\n[[authors]]\nname = \"Alice\"\n+++\n";
+///
+/// let (frontmatter, _) = markdown_frontmatter::parse::<Value>(doc).unwrap();
+/// assert_eq!(frontmatter.get("date"), Some(&Value::Datetime("1979-05-27".into())));
+/// assert_eq!(frontmatter.get("authors[0].name").and_then(Value::as_str), Some("Alice"));
+/// ```
+///
+/// The value implements [`Deserializer`], so it can be deserialized into a
+/// concrete type later, datetimes are deserialized as strings. They're
+/// serialized as strings too, except into TOML frontmatter, e.g. by
+/// [`to_string`](crate::to_string).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// JSON `null` or YAML `~`.
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// An integer, unsigned ones out of its range are stored as floats.
+    Integer(i64),
+    /// A floating point number.
+    Float(f64),
+    /// A string.
+    String(String),
+    /// TOML datetime in its RFC 3339 form.
+    Datetime(String),
+    /// An array of values.
+    Array(Vec<Value>),
+    /// A table of values.
+    Table(Table),
+    /// YAML tagged value, the tag is stored without the leading `!`.
+    Tagged(String, Box<Value>),
+}
+
+impl Value {
+    /// Returns a nested value by its path, e.g. `authors[0].name`.
+    ///
+    /// Tables are indexed by dot-separated keys and arrays by bracketed
+    /// indexes, tagged values are looked through. An empty path returns the
+    /// value itself. Keys containing dots or brackets can be accessed with
+    /// [`Table::get`].
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        path.split('.').try_fold(self, |value, segment| {
+            let (key, mut indexes) = segment.split_at(segment.find('[').unwrap_or(segment.len()));
+            let mut value = if key.is_empty() {
+                value
+            } else {
+                value.untagged().as_table()?.get(key)?
+            };
+            while !indexes.is_empty() {
+                let (index, rest) = indexes.strip_prefix('[')?.split_once(']')?;
+                value = value
+                    .untagged()
+                    .as_array()?
+                    .get(index.parse::<usize>().ok()?)?;
+                indexes = rest;
+            }
+            Some(value)
+        })
+    }
+
+    /// Returns `true` if the value is [`Value::Null`].
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns the boolean, if the value is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer, if the value is one.
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Self::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the float, if the value is a number.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(f) => Some(*f),
+            #[expect(clippy::cast_precision_loss)]
+            Self::Integer(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if the value is one.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the datetime in its RFC 3339 form, if the value is one.
+    pub fn as_datetime(&self) -> Option<&str> {
+        match self {
+            Self::Datetime(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the array, if the value is one.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Self::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the table, if the value is one.
+    pub fn as_table(&self) -> Option<&Table> {
+        match self {
+            Self::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    /// Returns the tag, if the value is tagged.
+    pub fn tag(&self) -> Option<&str> {
+        match self {
+            Self::Tagged(tag, _) => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// Returns the value without its tags.
+    pub fn untagged(&self) -> &Value {
+        match self {
+            Self::Tagged(_, value) => value.untagged(),
+            value => value,
+        }
+    }
+}
+
+/// A table of values, preserving the order of keys.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table(Vec<(String, Value)>);
+
+impl Table {
+    /// Returns the value of a key.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the table has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the entries in their original order.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.0.iter().map(|(key, value)| (key, value))
+    }
+}
+
+impl IntoIterator for Table {
+    type Item = (String, Value);
+    type IntoIter = std::vec::IntoIter<(String, Value)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl FromIterator<(String, Value)> for Table {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Null => serializer.serialize_unit(),
+            Self::Bool(b) => serializer.serialize_bool(*b),
+            Self::Integer(i) => serializer.serialize_i64(*i),
+            Self::Float(f) => serializer.serialize_f64(*f),
+            Self::String(s) => serializer.serialize_str(s),
+            Self::Datetime(s) => {
+                #[cfg(feature = "toml")]
+                if TOML_DATETIMES.get()
+                    && let Ok(datetime) = s.parse::<toml::value::Datetime>()
+                {
+                    return datetime.serialize(serializer);
+                }
+                serializer.serialize_str(s)
+            }
+            Self::Array(items) => serializer.collect_seq(items),
+            Self::Table(table) => serializer.collect_map(table.iter()),
+            Self::Tagged(tag, value) => {
+                // A single-entry map with a `!`-prefixed key collected as a
+                // string is what `serde_yaml` serializes as a tag
+                struct Tag<'a>(&'a str);
+
+                impl Serialize for Tag<'_> {
+                    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        serializer.collect_str(&format_args!("!{}", self.0))
+                    }
+                }
+
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(&Tag(tag), value)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any frontmatter value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::Integer(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        // Integers out of the `i64` range lose precision like in JSON parsers
+        #[expect(clippy::cast_precision_loss)]
+        Ok(i64::try_from(v).map_or(Value::Float(v as f64), Value::Integer))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::Float(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.into()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some(Key(key)) = map.next_key()? {
+            if entries.is_empty() && key == TOML_DATETIME_FIELD {
+                return Ok(Value::Datetime(map.next_value()?));
+            }
+            entries.push((key, map.next_value()?));
+        }
+        Ok(Value::Table(Table(entries)))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Value, A::Error> {
+        let (tag, variant) = data.variant::<String>()?;
+        let tag = match tag.strip_prefix('!') {
+            Some(tag) if !tag.is_empty() => tag.into(),
+            _ => tag,
+        };
+        Ok(Value::Tagged(tag, Box::new(variant.newtype_variant()?)))
+    }
+}
+
+impl<'de> Deserializer<'de> for Value {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Null => visitor.visit_unit(),
+            Self::Bool(b) => visitor.visit_bool(b),
+            Self::Integer(i) => visitor.visit_i64(i),
+            Self::Float(f) => visitor.visit_f64(f),
+            Self::String(s) | Self::Datetime(s) => visitor.visit_string(s),
+            Self::Array(items) => {
+                let mut seq = SeqDeserializer::new(items.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Self::Table(table) => {
+                let mut map = MapDeserializer::new(table.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            Self::Tagged(tag, value) => visitor.visit_enum(Variant(tag, *value)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::String(variant) => visitor.visit_enum(Variant(variant, Self::Null)),
+            // Externally tagged variant
+            Self::Table(table) if table.len() == 1 => {
+                let (variant, value) = table.into_iter().next().expect("single entry");
+                visitor.visit_enum(Variant(variant, value))
+            }
+            value => value.deserialize_any(visitor).map_err(|e| {
+                de::Error::custom(format_args!(
+                    "{e} for enum {name}, expected one of {variants:?}"
+                ))
+            }),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl IntoDeserializer<'_, de::value::Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// An enum variant, a YAML tag or a key of a single-entry table, with its
+/// content.
+struct Variant(String, Value);
+
+impl<'de> EnumAccess<'de> for Variant {
+    type Error = de::value::Error;
+    type Variant = Value;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Value), Self::Error> {
+        let variant = seed.deserialize(self.0.into_deserializer())?;
+        Ok((variant, self.1))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Value {
+    type Error = de::value::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self {
+            Self::Null => Ok(()),
+            value => Err(de::Error::invalid_type(value.unexpected(), &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+}
+
+impl Value {
+    fn unexpected(&self) -> de::Unexpected<'_> {
+        match self {
+            Self::Null => de::Unexpected::Unit,
+            Self::Bool(b) => de::Unexpected::Bool(*b),
+            Self::Integer(i) => de::Unexpected::Signed(*i),
+            Self::Float(f) => de::Unexpected::Float(*f),
+            Self::String(s) | Self::Datetime(s) => de::Unexpected::Str(s),
+            Self::Array(_) => de::Unexpected::Seq,
+            Self::Table(_) => de::Unexpected::Map,
+            Self::Tagged(..) => de::Unexpected::Enum,
+        }
+    }
+}
+
+/// A table key, scalar YAML keys are converted into strings.
+struct Key(String);
+
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(KeyVisitor)
+    }
+}
+
+struct KeyVisitor;
+
+impl Visitor<'_> for KeyVisitor {
+    type Value = Key;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a scalar key")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Key, E> {
+        Ok(Key(v.to_string()))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Key, E> {
+        Ok(Key(v.to_string()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Key, E> {
+        Ok(Key(v.to_string()))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Key, E> {
+        Ok(Key(v.to_string()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Key, E> {
+        Ok(Key(v.into()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Key, E> {
+        Ok(Key(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Key, E> {
+        Ok(Key("null".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_datetime() {
+        let value: Value = toml::from_str("a = 1979-05-27T07:32:00Z").unwrap();
+        let Value::Table(table) = &value else {
+            panic!("not a table: {value:?}")
+        };
+        assert_eq!(
+            table.iter().next().unwrap(),
+            (
+                &"a".to_owned(),
+                &Value::Datetime("1979-05-27T07:32:00Z".into())
+            )
+        );
+        assert_eq!(
+            crate::FrontmatterFormat::Toml.serialize(&value).unwrap(),
+            "a = 1979-05-27T07:32:00Z\n"
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_datetime() {
+        let value = table([("d", Value::Datetime("1979-05-27".into()))]);
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"d":"1979-05-27"}"#
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_tags_and_keys() {
+        let value: Value = serde_yaml::from_str("1: !foo bar\nb: [x, !baz {c: 1}]\n").unwrap();
+        assert_eq!(
+            value,
+            Value::Table(Table(vec![
+                (
+                    "1".into(),
+                    Value::Tagged("foo".into(), Box::new(Value::String("bar".into())))
+                ),
+                (
+                    "b".into(),
+                    Value::Array(vec![
+                        Value::String("x".into()),
+                        Value::Tagged(
+                            "baz".into(),
+                            Box::new(Value::Table(Table(vec![("c".into(), Value::Integer(1))])))
+                        )
+                    ])
+                ),
+            ]))
+        );
+        assert_eq!(
+            serde_yaml::to_string(&value).unwrap(),
+            "'1': !foo bar\nb:\n- x\n- !baz\n  c: 1\n"
+        );
+    }
+
+    fn table(entries: impl IntoIterator<Item = (&'static str, Value)>) -> Value {
+        Value::Table(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn get_by_path() {
+        let value = table([
+            (
+                "a",
+                table([(
+                    "b",
+                    Value::Array(vec![
+                        Value::Integer(1),
+                        Value::Array(vec![Value::Bool(true)]),
+                    ]),
+                )]),
+            ),
+            (
+                "t",
+                Value::Tagged("x".into(), Box::new(table([("c", Value::Null)]))),
+            ),
+        ]);
+        assert_eq!(value.get(""), Some(&value));
+        assert_eq!(value.get("a.b[0]"), Some(&Value::Integer(1)));
+        assert_eq!(value.get("a.b[1][0]").and_then(Value::as_bool), Some(true));
+        assert!(value.get("t.c").unwrap().is_null());
+        assert_eq!(value.get("t").and_then(Value::tag), Some("x"));
+        assert_eq!(value.get("a.b[2]"), None);
+        assert_eq!(value.get("a.c"), None);
+        assert_eq!(value.get("a[0]"), None);
+        assert_eq!(value.get("a.b[x]"), None);
+        assert_eq!(value.get("a.b[0"), None);
+    }
+
+    #[test]
+    fn deserialize_from_value() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Frontmatter {
+            title: String,
+            draft: Option<bool>,
+            tags: Vec<String>,
+            date: String,
+            kind: Kind,
+            shape: Shape,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        enum Kind {
+            Post,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        enum Shape {
+            Circle { r: i64 },
+        }
+
+        let value = table([
+            ("title", Value::String("Hello".into())),
+            ("draft", Value::Null),
+            ("tags", Value::Array(vec![Value::String("a".into())])),
+            ("date", Value::Datetime("1979-05-27".into())),
+            ("kind", Value::String("Post".into())),
+            (
+                "shape",
+                Value::Tagged("Circle".into(), Box::new(table([("r", Value::Integer(1))]))),
+            ),
+        ]);
+        assert_eq!(
+            Frontmatter::deserialize(value.clone()).unwrap(),
+            Frontmatter {
+                title: "Hello".into(),
+                draft: None,
+                tags: vec!["a".into()],
+                date: "1979-05-27".into(),
+                kind: Kind::Post,
+                shape: Shape::Circle { r: 1 },
+            }
+        );
+        let shape = value.get("shape").unwrap();
+        assert_eq!(&Value::deserialize(shape.clone()).unwrap(), shape);
+        assert!(Frontmatter::deserialize(Value::Integer(1)).is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_keeps_key_order() {
+        let value: Value = serde_json::from_str(r#"{"b": 1, "a": [null, 1.5]}"#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"b":1,"a":[null,1.5]}"#
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn large_unsigned_integer() {
+        let value: Value = serde_json::from_str(r#"{"a": 1, "n": 18446744073709551615}"#).unwrap();
+        assert_eq!(value.get("a"), Some(&Value::Integer(1)));
+        assert_eq!(
+            value.get("n"),
+            Some(&Value::Float(18_446_744_073_709_551_615.0))
+        );
+    }
+}
@@ -0,0 +1,391 @@
+//! Watching a directory of documents for changes, see [`Watcher`].
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+use notify::Watcher as _;
+
+use crate::{Error, FrontmatterFormat, Parser, corpus::walk};
+
+/// A change of a document reported by [`Watcher`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Event {
+    /// A document was added.
+    Added {
+        /// Path of the document.
+        path: PathBuf,
+    },
+    /// A document was removed.
+    Removed {
+        /// Path of the document.
+        path: PathBuf,
+    },
+    /// Frontmatter of a document changed, it's reported along with a change of
+    /// the body, if any.
+    FrontmatterChanged {
+        /// Path of the document.
+        path: PathBuf,
+        /// Raw frontmatter before the change, `None` if there was none.
+        old: Option<String>,
+        /// Raw frontmatter after the change, `None` if there is none.
+        new: Option<String>,
+    },
+    /// Body of a document changed.
+    BodyChanged {
+        /// Path of the document.
+        path: PathBuf,
+    },
+    /// A document or a directory can't be read, or a document can't be split.
+    /// The document is compared to its last valid state on the next change.
+    ///
+    /// Errors of watching are reported for the root, along with the events of
+    /// a rescan, as notifications may have been lost.
+    Invalid {
+        /// Path of the document or the directory.
+        path: PathBuf,
+        /// The error.
+        error: Error,
+    },
+}
+
+/// Watches `*.md` files under a directory, reporting changes of their
+/// frontmatter and bodies.
+///
+/// Changed documents are split again, not parsed, so changes are reported
+/// for the raw frontmatter. Paths are reported under the canonicalized root.
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_frontmatter::watch::{Event, Watcher};
+///
+/// let mut watcher = Watcher::new("content").unwrap();
+/// loop {
+///     for event in watcher.recv().unwrap() {
+///         if let Event::FrontmatterChanged { path, .. } = event {
+///             println!("{} metadata changed", path.display());
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Watcher {
+    parser: Parser,
+    root: PathBuf,
+    documents: HashMap<PathBuf, Document>,
+    notifications: mpsc::Receiver<notify::Result<notify::Event>>,
+    // Stops watching when dropped
+    _watcher: notify::RecommendedWatcher,
+}
+
+/// Last valid state of a document.
+#[derive(Debug)]
+struct Document {
+    frontmatter: Option<(FrontmatterFormat, String)>,
+    body_hash: u64,
+}
+
+impl Watcher {
+    /// Starts watching a directory, documents already there aren't reported.
+    pub fn new(root: impl AsRef<Path>) -> Result<Self, Error> {
+        Parser::new().watch(root)
+    }
+
+    /// Waits for changes of the directory, returning the events of changed
+    /// documents, which may be none if only other files changed.
+    pub fn recv(&mut self) -> Result<Vec<Event>, Error> {
+        match self.notifications.recv() {
+            Ok(notification) => Ok(self.handle(notification)),
+            // The watcher is alive as long as the receiver
+            Err(mpsc::RecvError) => Ok(Vec::new()),
+        }
+    }
+
+    /// Waits for changes of the directory like [`recv`](Self::recv), but for
+    /// no longer than a timeout.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<Vec<Event>, Error> {
+        match self.notifications.recv_timeout(timeout) {
+            Ok(notification) => Ok(self.handle(notification)),
+            Err(mpsc::RecvTimeoutError::Timeout | mpsc::RecvTimeoutError::Disconnected) => {
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Compares the whole directory to the known state of documents,
+    /// returning the events of changed documents.
+    ///
+    /// It's done automatically if notifications were lost, but may be useful
+    /// on file systems without notifications, e.g. network ones.
+    pub fn rescan(&mut self) -> Vec<Event> {
+        let mut events = Vec::new();
+        let root = self.root.clone();
+        self.update(&root, &mut events);
+        events
+    }
+
+    /// Handles a notification along with the pending ones, so a burst of
+    /// notifications, e.g. of saving a file, is handled at once.
+    fn handle(&mut self, notification: notify::Result<notify::Event>) -> Vec<Event> {
+        let mut events = Vec::new();
+        let mut paths = Vec::new();
+        let mut rescan = false;
+        for notification in std::iter::once(notification).chain(self.notifications.try_iter()) {
+            match notification {
+                Ok(notification) => {
+                    rescan |= notification.need_rescan();
+                    paths.extend(notification.paths);
+                }
+                Err(e) => {
+                    events.push(Event::Invalid {
+                        path: self.root.clone(),
+                        error: Error::Watch(e),
+                    });
+                    rescan = true;
+                }
+            }
+        }
+        if rescan {
+            events.extend(self.rescan());
+            return events;
+        }
+        paths.sort();
+        paths.dedup();
+        for path in paths {
+            self.update(&path, &mut events);
+        }
+        events
+    }
+
+    /// Updates documents at a path, a document or a directory, either existing
+    /// or removed.
+    fn update(&mut self, path: &Path, events: &mut Vec<Event>) {
+        let mut found = Vec::new();
+        if path.is_dir() {
+            let mut errors = Vec::new();
+            walk(path, &mut found, &mut errors);
+            events.extend(
+                errors
+                    .into_iter()
+                    .map(|(path, error)| Event::Invalid { path, error }),
+            );
+            found.sort();
+        } else if path.is_file() && path.extension().is_some_and(|ext| ext == "md") {
+            found.push(path.to_owned());
+        }
+
+        let found_set: HashSet<_> = found.iter().collect();
+        let mut removed: Vec<_> = self
+            .documents
+            .keys()
+            .filter(|document| document.starts_with(path) && !found_set.contains(document))
+            .cloned()
+            .collect();
+        removed.sort();
+        for path in removed {
+            self.documents.remove(&path);
+            events.push(Event::Removed { path });
+        }
+
+        for path in found {
+            self.update_document(path, events);
+        }
+    }
+
+    fn update_document(&mut self, path: PathBuf, events: &mut Vec<Event>) {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                if self.documents.remove(&path).is_some() {
+                    events.push(Event::Removed { path });
+                }
+                return;
+            }
+            Err(e) => {
+                events.push(Event::Invalid {
+                    path,
+                    error: Error::Io(e),
+                });
+                return;
+            }
+        };
+        let document = match self.parser.split(&content) {
+            Ok((frontmatter, body)) => {
+                let mut hasher = DefaultHasher::new();
+                body.hash(&mut hasher);
+                Document {
+                    frontmatter: frontmatter.map(|f| (f.format(), f.as_str().to_owned())),
+                    body_hash: hasher.finish(),
+                }
+            }
+            Err(error) => {
+                events.push(Event::Invalid { path, error });
+                return;
+            }
+        };
+
+        let Some(old) = self.documents.get(&path) else {
+            self.documents.insert(path.clone(), document);
+            events.push(Event::Added { path });
+            return;
+        };
+        if old.frontmatter != document.frontmatter {
+            let matter = |document: &Document| {
+                document
+                    .frontmatter
+                    .as_ref()
+                    .map(|(_, matter)| matter.clone())
+            };
+            events.push(Event::FrontmatterChanged {
+                path: path.clone(),
+                old: matter(old),
+                new: matter(&document),
+            });
+        }
+        if old.body_hash != document.body_hash {
+            events.push(Event::BodyChanged { path: path.clone() });
+        }
+        self.documents.insert(path, document);
+    }
+}
+
+impl Parser {
+    /// Starts watching a directory like [`Watcher::new`].
+    pub fn watch(&self, root: impl AsRef<Path>) -> Result<Watcher, Error> {
+        let root = fs::canonicalize(root).map_err(Error::Io)?;
+        let (sender, notifications) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender).map_err(Error::Watch)?;
+        watcher
+            .watch(&root, notify::RecursiveMode::Recursive)
+            .map_err(Error::Watch)?;
+        let mut watcher = Watcher {
+            parser: self.clone(),
+            root,
+            documents: HashMap::new(),
+            notifications,
+            _watcher: watcher,
+        };
+        // Documents changed since the watching started are reported anyway
+        watcher.rescan();
+        Ok(watcher)
+    }
+}
+
+#[cfg(all(test, feature = "yaml"))]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    fn paths<'a>(events: &'a [Event], root: &Path) -> Vec<(&'static str, &'a Path)> {
+        events
+            .iter()
+            .map(|event| {
+                let (kind, path) = match event {
+                    Event::Added { path } => ("added", path),
+                    Event::Removed { path } => ("removed", path),
+                    Event::FrontmatterChanged { path, .. } => ("frontmatter", path),
+                    Event::BodyChanged { path } => ("body", path),
+                    Event::Invalid { path, .. } => ("invalid", path),
+                };
+                (kind, path.strip_prefix(root).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rescan() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        fs::write(root.join("a.md"), "---\ntitle: A\n---\nBody").unwrap();
+        fs::write(root.join("notes.txt"), "").unwrap();
+
+        let mut watcher = Watcher::new(&root).unwrap();
+        assert!(watcher.rescan().is_empty());
+
+        fs::create_dir(root.join("b")).unwrap();
+        fs::write(root.join("b/c.md"), "Body").unwrap();
+        fs::write(root.join("a.md"), "---\ntitle: A!\n---\nBody!").unwrap();
+        let events = watcher.rescan();
+        assert_eq!(
+            paths(&events, &root),
+            [
+                ("frontmatter", Path::new("a.md")),
+                ("body", Path::new("a.md")),
+                ("added", Path::new("b/c.md"))
+            ]
+        );
+        assert!(matches!(
+            &events[0],
+            Event::FrontmatterChanged { old: Some(old), new: Some(new), .. }
+                if old == "title: A\n" && new == "title: A!\n"
+        ));
+
+        fs::write(root.join("b/c.md"), "---\ntitle: C\n").unwrap();
+        fs::write(root.join("a.md"), "Body!").unwrap();
+        let events = watcher.rescan();
+        assert_eq!(
+            paths(&events, &root),
+            [
+                ("frontmatter", Path::new("a.md")),
+                ("invalid", Path::new("b/c.md"))
+            ]
+        );
+        assert!(matches!(
+            &events[0],
+            Event::FrontmatterChanged {
+                old: Some(_),
+                new: None,
+                ..
+            }
+        ));
+
+        fs::remove_dir_all(root.join("b")).unwrap();
+        let events = watcher.rescan();
+        assert_eq!(paths(&events, &root), [("removed", Path::new("b/c.md"))]);
+    }
+
+    #[test]
+    fn notifications() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let mut watcher = Watcher::new(&root).unwrap();
+
+        fs::write(root.join("a.md"), "---\ntitle: A\n---\nBody").unwrap();
+        let mut events = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while events.is_empty() && Instant::now() < deadline {
+            events = watcher.recv_timeout(Duration::from_millis(100)).unwrap();
+        }
+        assert_eq!(paths(&events, &root), [("added", Path::new("a.md"))]);
+    }
+
+    #[test]
+    fn notification_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let mut watcher = Watcher::new(&root).unwrap();
+
+        fs::write(root.join("a.md"), "---\ntitle: A\n---\nBody").unwrap();
+        let events = watcher.handle(Err(notify::Error::generic("lost")));
+        assert_eq!(
+            paths(&events, &root),
+            [("invalid", Path::new("")), ("added", Path::new("a.md"))]
+        );
+        assert!(matches!(
+            &events[0],
+            Event::Invalid {
+                error: Error::Watch(_),
+                ..
+            }
+        ));
+    }
+}